@@ -74,26 +74,26 @@ mod tests {
     use super::*;
 
     fn make_simple_expr<E>(expr: &dyn Expr<E>) -> E {
-        return expr.add(expr.i_val(2), expr.i_val(3));
+        expr.add(expr.i_val(2), expr.i_val(3))
     }
 
     fn make_complex_expr<E>(expr: &dyn Expr<E>) -> E {
-        return expr.add(expr.add(expr.i_val(2), expr.i_val(3)), expr.i_val(3));
+        expr.add(expr.add(expr.i_val(2), expr.i_val(3)), expr.i_val(3))
     }
 
     fn make_complex_mul_expr<E>(expr: &dyn MulExpr<E>) -> E {
-        return expr.mul(
+        expr.mul(
             expr.add(
                 expr.mul(expr.add(expr.i_val(1), expr.i_val(5)), expr.i_val(7)),
                 expr.i_val(3),
             ),
             expr.i_val(3),
-        );
+        )
     }
 
     // DOH! It compiles, but we are mixing boolean and integers!
     fn make_mixed_expr<E>(expr: &dyn BoolExpr<E>) -> E {
-        return expr.add(expr.add(expr.b(false), expr.i_val(3)), expr.i_val(3));
+        expr.add(expr.add(expr.b(false), expr.i_val(3)), expr.i_val(3))
     }
 
     #[test]
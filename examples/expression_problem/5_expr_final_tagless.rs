@@ -81,34 +81,34 @@ mod tests {
     where
         E: Expr,
     {
-        return E::add(E::i_val(2), E::i_val(3));
+        E::add(E::i_val(2), E::i_val(3))
     }
 
     fn make_complex_expr<E>() -> E::Repr<i32>
     where
         E: Expr,
     {
-        return E::add(E::add(E::i_val(2), E::i_val(3)), E::i_val(3));
+        E::add(E::add(E::i_val(2), E::i_val(3)), E::i_val(3))
     }
 
     fn make_complex_mul_expr<E>() -> E::Repr<i32>
     where
         E: MulExpr,
     {
-        return E::mul(
+        E::mul(
             E::add(E::add(E::i_val(2), E::i_val(3)), E::i_val(3)),
             E::mul(
                 E::add(E::add(E::i_val(2), E::i_val(3)), E::i_val(3)),
                 E::i_val(12),
             ),
-        );
+        )
     }
 
     fn make_complex_bool_expr<E>() -> E::Repr<bool>
     where
         E: BoolExpr,
     {
-        return E::and(E::b(false), E::b(true));
+        E::and(E::b(false), E::b(true))
     }
 
     // fn does_not_compile<E>() -> E::Repr<bool>
@@ -5,15 +5,15 @@ use super::{turtle::Turtle, turtle_api::TurtleError};
 pub type TurtleM<'a, A> =
     StateT<'a, Turtle, ResultT<Writer<Result<(A, Turtle), TurtleError>, String>>>;
 
-pub fn r#move(d: &str) -> TurtleM<()> {
+pub fn r#move(d: &str) -> TurtleM<'_, ()> {
     super::helpers::move_turtle(d)
 }
 
-pub fn turn(a: &str) -> TurtleM<()> {
+pub fn turn(a: &str) -> TurtleM<'_, ()> {
     super::helpers::turn_turtle(a)
 }
 
-pub fn set_pen_color(c: &str) -> TurtleM<()> {
+pub fn set_pen_color(c: &str) -> TurtleM<'_, ()> {
     super::helpers::set_turtle_color(c)
 }
 
@@ -1,4 +1,4 @@
-use algar::{Applicative, Apply};
+use algar::{Applicative, Apply, Validation};
 
 use crate::model::turtle::{Angle, Distance, PenColor, PenState, Turtle};
 
@@ -58,6 +58,19 @@ pub fn set_pen_state(turtle: Turtle, state: &str) -> Result<Turtle, TurtleError>
     )
 }
 
+/// Set both the pen color and state, reporting all the invalid values instead of the first one.
+pub fn set_pen(turtle: Turtle, color: &str, state: &str) -> Validation<Vec<TurtleError>, Turtle> {
+    Apply::lift_a2(
+        all_errors(validate_pen_color(color.to_string())),
+        all_errors(validate_pen_state(state.to_string())),
+        |color, state| turtle.set_pen_color(color).set_pen_state(state),
+    )
+}
+
+fn all_errors<A>(validation: Result<A, TurtleError>) -> Validation<Vec<TurtleError>, A> {
+    validation.map_err(|e| vec![e]).into()
+}
+
 pub fn validate_distance(d: String) -> Result<Distance, TurtleError> {
     match d.parse::<f32>() {
         Ok(v) => Result::Ok(Distance::new(v)),
@@ -90,7 +103,12 @@ pub fn validate_pen_state(s: String) -> Result<PenState, TurtleError> {
 
 #[cfg(test)]
 mod test {
-    use crate::model::{turtle::Turtle, turtle_api::TurtleError};
+    use algar::Validation;
+
+    use crate::model::{
+        turtle::{PenColor, PenState, Turtle},
+        turtle_api::TurtleError,
+    };
 
     #[test]
     fn test_api_move_ok() {
@@ -125,4 +143,25 @@ mod test {
             Result::Err(TurtleError::InvalidAngle)
         );
     }
+
+    #[test]
+    fn test_api_set_pen_all_errors() {
+        let t = Turtle::default();
+
+        assert_eq!(
+            super::set_pen(t.clone(), "red", "down"),
+            Validation::Success(
+                t.clone()
+                    .set_pen_color(PenColor::Red)
+                    .set_pen_state(PenState::Down)
+            )
+        );
+        assert_eq!(
+            super::set_pen(t, "blue", "sideways"),
+            Validation::Failure(vec![
+                TurtleError::InvalidPenColor("BLUE".to_string()),
+                TurtleError::InvalidPenState("SIDEWAYS".to_string())
+            ])
+        );
+    }
 }
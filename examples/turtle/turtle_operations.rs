@@ -7,17 +7,17 @@ fn main() {}
 
 #[cfg(test)]
 mod test {
-    use algar::{m, Monad, ResultT, State, StateT, Writer};
+    use algar::{
+        m, Lens, Monad, MonadError, MonadState, MonadTrans, ResultT, State, StateT, Writer,
+    };
 
     use crate::model::{
-        helpers::{lift_validation, move_turtle, run, set_turtle_color, turn_turtle},
+        helpers::{move_turtle, run, set_turtle_color, turn_turtle},
         turtle::{Angle, Distance, PenColor, PenState, Position, Turtle},
-        turtleM::{r#move, set_pen_color, turn},
+        turtleM::{r#move, set_pen_color, turn, TurtleM},
         turtle_api::{self, TurtleError},
     };
 
-    use crate::model::helpers::lift_op;
-
     #[test]
     fn test_plain_turtle() {
         let t = Turtle::default();
@@ -113,6 +113,39 @@ mod test {
         )
     }
 
+    #[test]
+    fn test_state_monad_zoom_turtle() {
+        let color = Lens::new(
+            |t: &Turtle| t.color.clone(),
+            |t, color| Turtle { color, ..t },
+        );
+        let state = Lens::new(
+            |t: &Turtle| t.state.clone(),
+            |t, state| Turtle { state, ..t },
+        );
+
+        // Each step only knows about the field it changes
+        let set_pen_color = |p: PenColor| State::new(move |_| ((), p));
+        let pen_down = State::new(|_| ((), PenState::Down));
+
+        let new_t = State::execute(
+            m! {
+                set_pen_color(PenColor::Red).zoom(color);
+                pen_down.zoom(state)
+            },
+            Turtle::default(),
+        );
+
+        assert_eq!(
+            new_t.1,
+            Turtle {
+                color: PenColor::Red,
+                state: PenState::Down,
+                ..Turtle::default()
+            }
+        )
+    }
+
     // #[test]
     // fn test_state_monad_api_turtle() {
     //     let do_move = |p| State::new(move |s_t: Turtle| ((), turtle_api::r#move(s_t, p)));
@@ -210,20 +243,27 @@ mod test {
 
     #[test]
     fn test_monad_trans_lift() {
-        let log = |log: &str| {
-            StateT::<Turtle, ResultT<Writer<Result<(), TurtleError>, String>>>::lift(ResultT::lift(
-                Writer::<Turtle, _>::tell(String::from(log)),
-            ))
+        // Each layer of the stack is climbed by one `lift`, inferred from the type of the result
+        let log = |log: &str| -> TurtleM<()> {
+            MonadTrans::lift(MonadTrans::lift(Writer::<Turtle, _>::tell(String::from(
+                log,
+            ))))
+        };
+        let validate_distance = |d: &str| -> TurtleM<Distance> {
+            MonadTrans::lift(ResultT::new(Writer::new(
+                turtle_api::validate_distance(d.to_string()),
+                String::new(),
+            )))
+        };
+        let validate_angle = |a: &str| -> TurtleM<Angle> {
+            MonadTrans::lift(ResultT::new(Writer::new(
+                turtle_api::validate_angle(a.to_string()),
+                String::new(),
+            )))
         };
 
-        let validate_distance = lift_validation(turtle_api::validate_distance);
-        let _validate_pen_color = lift_validation(turtle_api::validate_pen_color);
-        let validate_angle = lift_validation(turtle_api::validate_angle);
-
-        let do_move = lift_op(Turtle::r#move);
-        let do_turn = lift_op(Turtle::turn);
-        let _do_set_pen_color = lift_op(Turtle::set_pen_color);
-        let _do_set_pen_state = lift_op(Turtle::set_pen_state);
+        let do_move = |d: Distance| TurtleM::modify(move |t: Turtle| t.r#move(d));
+        let do_turn = |a: Angle| TurtleM::modify(move |t: Turtle| t.turn(a));
 
         let comp_t = m! {
             log("turning 90.0\n");
@@ -287,6 +327,34 @@ mod test {
         assert_eq!(new_t.0.err().unwrap(), TurtleError::InvalidDistance);
     }
 
+    #[test]
+    fn test_final_solution_recover_from_failure() {
+        let comp_t = m! {
+            turn_turtle("90.0");
+            move_turtle("NOT_VALID").catch_error(|e| {
+                assert_eq!(e, TurtleError::InvalidDistance);
+                move_turtle("1.0")
+            });
+            set_turtle_color("RED")
+        };
+
+        let new_t = run(comp_t, Turtle::default());
+
+        assert_eq!(
+            new_t.0.unwrap().1,
+            Turtle {
+                angle: Angle::new(90.0),
+                color: PenColor::Red,
+                state: PenState::Up,
+                position: Position::new(0.0, 1.0)
+            }
+        );
+        assert_eq!(
+            new_t.1,
+            "turning 90.0\nmoving NOT_VALID\nmoving 1.0\ncoloring pen to  RED\n"
+        );
+    }
+
     #[test]
     fn test_new_type_turtle_m() {
         // Hide transformer stack complexity behind a new type: TurtleM
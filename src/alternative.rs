@@ -0,0 +1,181 @@
+use crate::Monad;
+
+/// `Alternative` describes computations which can fail, and a choice between them:
+/// `empty` is the computation which always fails, and `a.alt(b)` tries `a`, falling back to `b` when `a` fails
+/// (or collects the results of both, for collections like `Vec`).
+///
+/// `some` and `many` run a computation over and over, collecting its results until it fails:
+/// they need the computation to be a `Monad`, and to eventually fail (e.g. a parser running out of input).
+/// On `Option` and `Vec`, which carry no effect, they only terminate for an empty computation.
+///
+/// `Alternative` doesn't extend `Applicative`, as `Vec` (whose `bind` would need to call its function
+/// more than once) is neither an `Apply` nor a `Monad` in this crate.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/base/docs/Control-Applicative.html#t:Alternative>
+///
+/// ```haskell
+/// class Applicative f => Alternative f where
+///   empty :: f a
+///   (<|>) :: f a -> f a -> f a
+///   some :: f a -> f [a]
+///   many :: f a -> f [a]
+/// ```
+pub trait Alternative<'a>: Sized {
+    /// The computation which always fails.
+    fn empty() -> Self;
+
+    /// Try this computation, else the other one.
+    fn alt(self, other: Self) -> Self;
+
+    /// One or more results of `v`.
+    #[allow(clippy::type_complexity)]
+    fn some<F>(v: F) -> <Self as Monad<'a>>::Wrapped<Vec<<Self as Monad<'a>>::Unwrapped>>
+    where
+        Self: Monad<'a>,
+        F: Fn() -> Self + Clone + 'a,
+        <Self as Monad<'a>>::Unwrapped: 'a,
+        <Self as Monad<'a>>::Wrapped<Vec<<Self as Monad<'a>>::Unwrapped>>: Alternative<'a>
+            + Monad<
+                'a,
+                Unwrapped = Vec<<Self as Monad<'a>>::Unwrapped>,
+                Wrapped<Vec<<Self as Monad<'a>>::Unwrapped>> = <Self as Monad<'a>>::Wrapped<
+                    Vec<<Self as Monad<'a>>::Unwrapped>,
+                >,
+            >,
+    {
+        v().bind(move |a| {
+            Self::many(v).bind::<_, Vec<<Self as Monad<'a>>::Unwrapped>>(move |mut rest| {
+                rest.insert(0, a);
+                Self::of(rest)
+            })
+        })
+    }
+
+    /// Zero or more results of `v`.
+    #[allow(clippy::type_complexity)]
+    fn many<F>(v: F) -> <Self as Monad<'a>>::Wrapped<Vec<<Self as Monad<'a>>::Unwrapped>>
+    where
+        Self: Monad<'a>,
+        F: Fn() -> Self + Clone + 'a,
+        <Self as Monad<'a>>::Unwrapped: 'a,
+        <Self as Monad<'a>>::Wrapped<Vec<<Self as Monad<'a>>::Unwrapped>>: Alternative<'a>
+            + Monad<
+                'a,
+                Unwrapped = Vec<<Self as Monad<'a>>::Unwrapped>,
+                Wrapped<Vec<<Self as Monad<'a>>::Unwrapped>> = <Self as Monad<'a>>::Wrapped<
+                    Vec<<Self as Monad<'a>>::Unwrapped>,
+                >,
+            >,
+    {
+        Self::some(v).alt(Self::of(vec![]))
+    }
+}
+
+/// `MonadPlus` is a `Monad` with a choice, which fails with `mzero` and chooses with `mplus`:
+/// the same operations as `Alternative`, under their monadic names.
+/// It should obey the laws:
+/// - `mzero().bind(f) == mzero()`
+/// - `m.bind(|_| mzero()) == mzero()` (when `m` has no other effect)
+///
+/// Haskell definition from <https://hackage.haskell.org/package/base/docs/Control-Monad.html#t:MonadPlus>
+///
+/// ```haskell
+/// class (Alternative m, Monad m) => MonadPlus m where
+///   mzero :: m a
+///   mplus :: m a -> m a -> m a
+/// ```
+pub trait MonadPlus<'a>: Alternative<'a> + Monad<'a> {
+    /// The failing computation, `empty`.
+    fn mzero() -> Self {
+        Self::empty()
+    }
+
+    /// Choice between two computations, `alt`.
+    fn mplus(self, other: Self) -> Self {
+        self.alt(other)
+    }
+}
+
+impl<'a, A> Alternative<'a> for Option<A> {
+    fn empty() -> Self {
+        None
+    }
+
+    fn alt(self, other: Self) -> Self {
+        self.or(other)
+    }
+}
+
+impl<'a, A> MonadPlus<'a> for Option<A> {}
+
+impl<'a, A> Alternative<'a> for Vec<A> {
+    fn empty() -> Self {
+        vec![]
+    }
+
+    fn alt(mut self, other: Self) -> Self {
+        self.extend(other);
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::m;
+    use crate::Alternative;
+    use crate::Monad;
+    use crate::MonadPlus;
+
+    #[test]
+    fn option_alternative() {
+        assert_eq!(Some(1).alt(Some(2)), Some(1));
+        assert_eq!(None.alt(Some(2)), Some(2));
+        assert_eq!(Option::<i32>::empty().alt(None), None);
+        assert_eq!(Option::<i32>::mzero().mplus(Some(3)), Some(3));
+
+        assert_eq!(Option::<i32>::many(|| None), Some(vec![]));
+        assert_eq!(Option::<i32>::some(|| None), None);
+    }
+
+    #[test]
+    fn vec_alternative() {
+        assert_eq!(vec![1, 2].alt(vec![3]), vec![1, 2, 3]);
+        assert_eq!(Vec::<i32>::empty().alt(vec![3]), vec![3]);
+    }
+
+    #[test]
+    fn guard_in_m() {
+        let safe_div = |a: i32, b: i32| {
+            m! {
+                guard!(b != 0);
+                Some(a / b)
+            }
+        };
+
+        assert_eq!(safe_div(84, 2), Some(42));
+        assert_eq!(safe_div(84, 0), None);
+
+        let even_half = |n: i32| {
+            m! {
+                h <- safe_div(n, 2);
+                guard!(h * 2 == n);
+                Some(h)
+            }
+        };
+
+        assert_eq!(even_half(42), Some(21));
+        assert_eq!(even_half(41), None);
+
+        // A function named guard is still an ordinary statement
+        let guard = |ok: bool| if ok { Some(()) } else { None };
+        let checked = |n: i32| {
+            m! {
+                guard(n > 0);
+                Some(n)
+            }
+        };
+
+        assert_eq!(checked(1), Some(1));
+        assert_eq!(checked(-1), None);
+    }
+}
@@ -32,6 +32,7 @@ impl<'a, A> Apply<'a> for Option<A> {
     where
         F: FnOnce(Self::Unwrapped) -> B + 'a,
     {
+        #[allow(clippy::manual_map)]
         match self {
             Some(x) => match f {
                 Some(z) => Some(z(x)),
@@ -47,6 +48,7 @@ impl<'a, A> Apply<'a> for Option<A> {
     where
         F: FnOnce(Self::Unwrapped, B) -> C,
     {
+        #[allow(clippy::manual_map)]
         match self {
             Some(x) => match b {
                 Some(y) => Some(f(x, y)),
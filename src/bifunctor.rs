@@ -0,0 +1,101 @@
+/// A `Bifunctor` is a type constructor with two type parameters, each of which can be mapped over,
+/// like a `Result` (its error and its value) or a pair.
+///
+/// `first` maps the first type parameter in the Haskell order, which for `Result` (`Either e a`)
+/// is the error, and `second` the other one. `bimap` maps both at once.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/base/docs/Data-Bifunctor.html>
+///
+/// ```haskell
+/// class Bifunctor p where
+///   bimap :: (a -> b) -> (c -> d) -> p a c -> p b d
+///   first :: (a -> b) -> p a c -> p b c
+///   second :: (b -> c) -> p a b -> p a c
+/// ```
+pub trait Bifunctor<'a>: Sized {
+    /// The type mapped over by `first`
+    type First: 'a;
+
+    /// The type mapped over by `second`
+    type Second: 'a;
+
+    /// Target of the bimap operation. Like `Self`, but with different types for both sides.
+    type Wrapped<C: 'a, D: 'a>;
+
+    /// Map both sides at once.
+    fn bimap<F, G, C: 'a, D: 'a>(self, f: F, g: G) -> Self::Wrapped<C, D>
+    where
+        F: FnOnce(Self::First) -> C + 'a,
+        G: FnOnce(Self::Second) -> D + 'a;
+
+    /// Map the first side only.
+    fn first<F, C: 'a>(self, f: F) -> Self::Wrapped<C, Self::Second>
+    where
+        F: FnOnce(Self::First) -> C + 'a,
+    {
+        self.bimap(f, |b| b)
+    }
+
+    /// Map the second side only.
+    fn second<G, D: 'a>(self, g: G) -> Self::Wrapped<Self::First, D>
+    where
+        G: FnOnce(Self::Second) -> D + 'a,
+    {
+        self.bimap(|a| a, g)
+    }
+}
+
+impl<'a, A: 'a, E: 'a> Bifunctor<'a> for Result<A, E> {
+    type First = E;
+    type Second = A;
+    type Wrapped<C: 'a, D: 'a> = Result<D, C>;
+
+    fn bimap<F, G, C: 'a, D: 'a>(self, f: F, g: G) -> Self::Wrapped<C, D>
+    where
+        F: FnOnce(Self::First) -> C + 'a,
+        G: FnOnce(Self::Second) -> D + 'a,
+    {
+        match self {
+            Ok(a) => Ok(g(a)),
+            Err(e) => Err(f(e)),
+        }
+    }
+}
+
+impl<'a, A: 'a, B: 'a> Bifunctor<'a> for (A, B) {
+    type First = A;
+    type Second = B;
+    type Wrapped<C: 'a, D: 'a> = (C, D);
+
+    fn bimap<F, G, C: 'a, D: 'a>(self, f: F, g: G) -> Self::Wrapped<C, D>
+    where
+        F: FnOnce(Self::First) -> C + 'a,
+        G: FnOnce(Self::Second) -> D + 'a,
+    {
+        (f(self.0), g(self.1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Bifunctor;
+
+    #[test]
+    fn result_bifunctor() {
+        let ok: Result<i32, &str> = Ok(41);
+        assert_eq!(ok.bimap(|e| e.len(), |a| a + 1), Ok(42));
+        assert_eq!(ok.first(|e| e.to_uppercase()), Ok(41));
+
+        let err: Result<i32, &str> = Err("boom");
+        assert_eq!(err.bimap(|e| e.len(), |a| a + 1), Err(4));
+        assert_eq!(err.second(|a| a.to_string()), Err("boom"));
+    }
+
+    #[test]
+    fn tuple_bifunctor() {
+        let pair = ("answer", 41);
+        assert_eq!(pair.bimap(|s| s.len(), |n| n + 1), (6, 42));
+        assert_eq!(pair.first(|s| s.to_uppercase()), ("ANSWER".to_string(), 41));
+        assert_eq!(pair.second(|n| n * 2), ("answer", 82));
+    }
+}
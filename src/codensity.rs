@@ -0,0 +1,313 @@
+use std::ops::ControlFlow;
+
+use crate::{
+    Applicative, Apply, Eval, Free, Functor, FunctorOnce, FunctorOnceWrapped, Monad, MonadRec,
+    NaturalTransformation,
+};
+
+/// The rest of a `Codensity` program, from a value to the final `Free` program.
+type Continuation<'a, F, A, R> = Box<dyn 'a + FnOnce(A) -> Eval<'a, Free<'a, F, R>>>;
+
+/// `Free` in continuation-passing style: a program which, given the rest of the program,
+/// builds the whole `Free` program returning an R.
+///
+/// Binding a `Free` program walks it to the end to append the continuation, so a program
+/// built by left-nested binds (e.g. in a loop) costs quadratic time. `Codensity` only stacks
+/// continuations, so every `bind` is O(1): build the program with `Codensity`, then `lower` it
+/// to `Free` (or `fold_map` it directly) in linear time.
+///
+/// Haskell's `forall r` has no equivalent in Rust, so the type R of the final result of the
+/// whole program is a parameter: a program returning an A is usually a `Codensity<'a, F, A, A>`,
+/// built from helpers generic over R. Continuations are run through `Eval`, so lowering
+/// a long chain of binds is stack-safe as long as the instructions keep the rest of the program
+/// in a closure: mapping over a strict instruction, like `Put(String, String, A)`, builds the rest right away.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/kan-extensions/docs/Control-Monad-Codensity.html>
+///
+/// ```haskell
+/// newtype Codensity m a = Codensity { runCodensity :: forall b. (a -> m b) -> m b }
+/// ```
+#[allow(clippy::type_complexity)]
+pub struct Codensity<'a, F, A, R>
+where
+    F: FunctorOnce<'a> + 'a,
+    R: 'a,
+{
+    runner: Box<dyn 'a + FnOnce(Continuation<'a, F, A, R>) -> Eval<'a, Free<'a, F, R>>>,
+}
+
+impl<'a, F, A: 'a, R: 'a> Codensity<'a, F, A, R>
+where
+    F: FunctorOnceWrapped<'a> + 'a,
+{
+    fn new<G>(runner: G) -> Self
+    where
+        G: FnOnce(Continuation<'a, F, A, R>) -> Eval<'a, Free<'a, F, R>> + 'a,
+    {
+        Codensity {
+            runner: Box::new(runner),
+        }
+    }
+
+    fn run<G>(self, k: G) -> Eval<'a, Free<'a, F, R>>
+    where
+        G: FnOnce(A) -> Eval<'a, Free<'a, F, R>> + 'a,
+    {
+        (self.runner)(Box::new(k))
+    }
+
+    /// A program without instructions.
+    pub fn pure(value: A) -> Self {
+        Codensity::new(move |k| k(value))
+    }
+
+    /// Lift a single instruction into a program which runs it and returns its result.
+    pub fn lift_f(command: F::Wrapped<A>) -> Self {
+        Codensity::improve(Free::lift_f(command))
+    }
+
+    /// Turn a `Free` program into a `Codensity` one, to bind it cheaply.
+    pub fn improve(program: Free<'a, F, A>) -> Self {
+        Codensity::new(move |k| match program {
+            Free::Pure(a) => k(a),
+            program => Eval::now(program.bind(move |a| k(a).run())),
+        })
+    }
+}
+
+impl<'a, F, A: 'a> Codensity<'a, F, A, A>
+where
+    F: FunctorOnceWrapped<'a> + 'a,
+{
+    /// Turn the program back into a `Free` program.
+    pub fn lower(self) -> Free<'a, F, A> {
+        self.run(|a| Eval::now(Free::Pure(a))).run()
+    }
+
+    /// Interpret the program into the monad G, translating every instruction with `nt`: see `Free::fold_map`.
+    #[allow(clippy::type_complexity)]
+    pub fn fold_map<G, N>(self, nt: N) -> G::Wrapped<A>
+    where
+        G: MonadRec<'a>,
+        N: NaturalTransformation<'a, F, G> + 'a,
+        N::Target<Free<'a, F, A>>: Monad<
+            'a,
+            Unwrapped = Free<'a, F, A>,
+            Wrapped<ControlFlow<A, Free<'a, F, A>>> = G::Wrapped<ControlFlow<A, Free<'a, F, A>>>,
+        >,
+    {
+        self.lower().fold_map(nt)
+    }
+
+    /// Change the instruction functor of the program from F to G, translating every instruction with `nt`.
+    pub fn hoist<G, N>(self, nt: N) -> Codensity<'a, G, A, A>
+    where
+        G: FunctorOnceWrapped<'a> + 'a,
+        N: NaturalTransformation<'a, F, G, Target<Free<'a, F, A>> = G::Wrapped<Free<'a, F, A>>>
+            + 'a,
+    {
+        Codensity::improve(self.lower().hoist(nt))
+    }
+}
+
+impl<'a, F, A: 'a, R: 'a> Functor<'a> for Codensity<'a, F, A, R>
+where
+    F: FunctorOnceWrapped<'a> + 'a,
+{
+    type Unwrapped = A;
+    type Wrapped<B: 'a> = Codensity<'a, F, B, R>;
+
+    fn fmap<G, B: 'a>(self, f: G) -> Self::Wrapped<B>
+    where
+        G: Fn(Self::Unwrapped) -> B + 'a,
+    {
+        Codensity::new(move |k| self.run(move |a| k(f(a))))
+    }
+}
+
+impl<'a, F, A: 'a, R: 'a> Apply<'a> for Codensity<'a, F, A, R>
+where
+    F: FunctorOnceWrapped<'a> + 'a,
+{
+    fn ap<G, B: 'a>(self, f: Self::Wrapped<G>) -> Self::Wrapped<B>
+    where
+        G: FnOnce(Self::Unwrapped) -> B + 'a,
+    {
+        self.lift_a2(f, |a, f| f(a))
+    }
+
+    fn lift_a2<G, B: 'a, C: 'a>(self, b: Self::Wrapped<B>, f: G) -> Self::Wrapped<C>
+    where
+        G: FnOnce(Self::Unwrapped, B) -> C + 'a,
+    {
+        self.bind(move |a1| b.bind(move |a2| Codensity::pure(f(a1, a2))))
+    }
+}
+
+impl<'a, F, A: 'a, R: 'a> Applicative<'a> for Codensity<'a, F, A, R>
+where
+    F: FunctorOnceWrapped<'a> + 'a,
+{
+    fn of(value: Self::Unwrapped) -> Self::Wrapped<Self::Unwrapped> {
+        Codensity::pure(value)
+    }
+}
+
+impl<'a, F, A: 'a, R: 'a> Monad<'a> for Codensity<'a, F, A, R>
+where
+    F: FunctorOnceWrapped<'a> + 'a,
+{
+    type Unwrapped = A;
+    type Wrapped<T: 'a> = Codensity<'a, F, T, R>;
+
+    fn bind<G, B: 'a>(self, f: G) -> Self::Wrapped<B>
+    where
+        G: FnOnce(Self::Unwrapped) -> Self::Wrapped<B> + 'a,
+    {
+        // Codensity m >>= f = Codensity (\k -> m (\a -> runCodensity (f a) k))
+        Codensity::new(move |k| {
+            Eval::defer(move || self.run(move |a| Eval::defer(move || f(a).run(k))))
+        })
+    }
+
+    fn of<T: 'a>(value: T) -> Self::Wrapped<T> {
+        Codensity::pure(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use crate::{
+        Applicative, Apply, Codensity, Free, Functor, FunctorOnce, FunctorOnceWrapped, Monad,
+        NaturalTransformation, State,
+    };
+
+    thread_local! {
+        /// How many times an instruction has been mapped over
+        static FMAPS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    pub enum CounterF<'a, A> {
+        Incr(u64, Box<dyn 'a + FnOnce() -> A>),
+        Get(Box<dyn 'a + FnOnce(u64) -> A>),
+    }
+
+    impl<'a, A: 'a> FunctorOnce<'a> for CounterF<'a, A> {
+        type Unwrapped = A;
+
+        type Wrapped<B: 'a> = CounterF<'a, B>;
+
+        fn fmap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+        where
+            F: FnOnce(Self::Unwrapped) -> B + 'a,
+        {
+            FMAPS.with(|fmaps| fmaps.set(fmaps.get() + 1));
+            match self {
+                CounterF::Incr(n, next) => CounterF::Incr(n, Box::new(move || f(next()))),
+                CounterF::Get(cont) => CounterF::Get(Box::new(move |n| f(cont(n)))),
+            }
+        }
+    }
+
+    impl<'a, A: 'a> FunctorOnceWrapped<'a> for CounterF<'a, A> {
+        fn fmap_wrapped<X: 'a, Y: 'a, F>(fx: CounterF<'a, X>, f: F) -> CounterF<'a, Y>
+        where
+            F: FnOnce(X) -> Y + 'a,
+        {
+            fx.fmap(f)
+        }
+    }
+
+    type Counter<'a, A, R> = Codensity<'a, CounterF<'a, ()>, A, R>;
+
+    fn incr<'a, R: 'a>(n: u64) -> Counter<'a, (), R> {
+        Codensity::lift_f(CounterF::Incr(n, Box::new(|| ())))
+    }
+
+    fn get<'a, R: 'a>() -> Counter<'a, u64, R> {
+        Codensity::lift_f(CounterF::Get(Box::new(|n| n)))
+    }
+
+    struct InMemory;
+
+    impl<'a> NaturalTransformation<'a, CounterF<'a, ()>, State<'a, u64, ()>> for InMemory {
+        type Target<X: 'a> = State<'a, u64, X>;
+
+        fn transform<X: 'a>(&mut self, fx: CounterF<'a, X>) -> State<'a, u64, X> {
+            match fx {
+                CounterF::Incr(n, next) => State::new(move |s| (next(), s + n)),
+                CounterF::Get(cont) => State::new(move |s| (cont(s), s)),
+            }
+        }
+    }
+
+    fn fmaps() -> usize {
+        FMAPS.with(|fmaps| fmaps.replace(0))
+    }
+
+    #[test]
+    fn codensity_fold_map() {
+        let prog: Counter<String, String> = incr(1)
+            .bind(|_| incr(41))
+            .bind(|_| get())
+            .fmap(|n| format!("counter: {}", n));
+
+        assert_eq!(
+            prog.fold_map(InMemory).execute(0),
+            ("counter: 42".to_string(), 42)
+        );
+    }
+
+    #[test]
+    fn codensity_improve_lower() {
+        let free = Free::lift_f(CounterF::Incr(2, Box::new(|| ())))
+            .bind(|_| Free::lift_f(CounterF::Get(Box::new(|n| n))));
+        let improved: Counter<u64, u64> =
+            Codensity::improve(free).bind(|n| incr(n).fmap(move |_| n));
+
+        let lowered = improved.lower();
+        assert!(matches!(lowered, Free::Free(_)));
+        assert_eq!(lowered.fold_map(InMemory).execute(1), (3, 6));
+
+        let pure: Counter<u64, u64> = Codensity::improve(Free::Pure(42));
+        assert!(matches!(pure.lower(), Free::Pure(42)));
+    }
+
+    #[test]
+    fn codensity_apply_applicative() {
+        let prog: Counter<u64, u64> = incr(5)
+            .lift_a2(get(), |_, n| n)
+            .ap(<Counter<_, _> as Applicative>::of(|n: u64| n * 2));
+
+        assert_eq!(prog.fold_map(InMemory).execute(1), (12, 6));
+    }
+
+    #[test]
+    fn codensity_left_nested_binds_are_linear() {
+        // Left-nested binds on Free map over the rest of the program at every step
+        const SMALL: u64 = 1_000;
+        let mut free = Free::Pure(());
+        for i in 0..SMALL {
+            free = free.bind(move |_| Free::lift_f(CounterF::Incr(i, Box::new(|| ()))));
+        }
+        assert_eq!(
+            free.fold_map(InMemory).execute(0).1,
+            SMALL * (SMALL - 1) / 2
+        );
+        assert!(fmaps() > (SMALL * SMALL / 2) as usize);
+
+        // while every instruction of a Codensity program is only mapped over a constant number of times
+        const STEPS: u64 = 100_000;
+        let mut prog: Counter<(), ()> = Codensity::pure(());
+        for i in 0..STEPS {
+            prog = prog.bind(move |_| incr(i));
+        }
+        assert_eq!(
+            prog.fold_map(InMemory).execute(0).1,
+            STEPS * (STEPS - 1) / 2
+        );
+        assert!(fmaps() <= 3 * STEPS as usize);
+    }
+}
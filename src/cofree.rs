@@ -0,0 +1,417 @@
+use std::rc::Rc;
+
+use crate::{Comonad, Free, Functor, FunctorOnce, FunctorWrapped};
+
+/// The cofree comonad: a value (the `head`) paired with an F-shaped structure of more `Cofree`s (the `tail`).
+///
+/// It is the dual of `Free`: where a free monad is a program (a tree of instructions ending with values),
+/// a cofree comonad is a tree with a value at every node, e.g.
+///
+/// * an AST whose every node is annotated with some attribute (a type, a span, its value), with `unfold`
+/// * an interpreter, which answers every instruction of a `Free` program with its next state, with `coiter` and `pair`
+///
+/// As with `Free`, F is an instance of the functor (e.g. `ExprF<()>`) naming the shape of the tree.
+/// The tail is lazy, since a cofree interpreter is usually infinite: it is rebuilt every time it is forced,
+/// so that `Cofree` values can be cloned and refocused by `extend` for free.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/free/docs/Control-Comonad-Cofree.html>
+///
+/// ```haskell
+/// data Cofree f a = a :< f (Cofree f a)
+/// ```
+pub struct Cofree<'a, F, A: 'a>
+where
+    F: Functor<'a> + 'a,
+{
+    head: A,
+    tail: Rc<dyn 'a + Fn() -> F::Wrapped<Cofree<'a, F, A>>>,
+}
+
+impl<'a, F, A: 'a> Cofree<'a, F, A>
+where
+    F: Functor<'a> + 'a,
+{
+    /// Constructs a new `Cofree` from its head and a function building its tail.
+    pub fn new<T>(head: A, tail: T) -> Self
+    where
+        T: Fn() -> F::Wrapped<Cofree<'a, F, A>> + 'a,
+    {
+        Cofree {
+            head,
+            tail: Rc::new(tail),
+        }
+    }
+
+    /// The value at the root.
+    pub fn head(&self) -> &A {
+        &self.head
+    }
+
+    /// The children of the root.
+    pub fn tail(&self) -> F::Wrapped<Cofree<'a, F, A>> {
+        (self.tail)()
+    }
+
+    /// Grow a tree from a seed: `f` gives the value of each node and the seeds of its children.
+    pub fn unfold<B: 'a, U>(seed: B, f: U) -> Self
+    where
+        F: FunctorWrapped<'a>,
+        U: Fn(B) -> (A, F::Wrapped<B>) + 'a,
+        F::Wrapped<B>: Clone,
+    {
+        Cofree::unfold_with(seed, Rc::new(f))
+    }
+
+    fn unfold_with<B: 'a, U>(seed: B, f: Rc<U>) -> Self
+    where
+        F: FunctorWrapped<'a>,
+        U: Fn(B) -> (A, F::Wrapped<B>) + 'a,
+        F::Wrapped<B>: Clone,
+    {
+        let (head, seeds) = f(seed);
+        Cofree::new(head, move || {
+            let f = f.clone();
+            F::fmap_wrapped(seeds.clone(), move |b| Cofree::unfold_with(b, f.clone()))
+        })
+    }
+
+    /// Grow a tree where every node holds its own seed, e.g. the states of an interpreter.
+    pub fn coiter<U>(seed: A, f: U) -> Self
+    where
+        A: Clone,
+        F: FunctorWrapped<'a>,
+        U: Fn(A) -> F::Wrapped<A> + 'a,
+        F::Wrapped<A>: Clone,
+    {
+        Cofree::unfold(seed, move |a: A| (a.clone(), f(a)))
+    }
+
+    /// Run a `Free` program against this interpreter, returning its final state and the result of the program.
+    ///
+    /// `pairing` answers an instruction of the program with the matching branch of the interpreter,
+    /// giving back the next state of both (in Haskell, the `Pairing` of the two functors).
+    pub fn pair<G, B, P>(self, program: Free<'a, G, B>, mut pairing: P) -> (A, B)
+    where
+        G: FunctorOnce<'a> + 'a,
+        P: FnMut(
+            F::Wrapped<Cofree<'a, F, A>>,
+            G::Wrapped<Free<'a, G, B>>,
+        ) -> (Cofree<'a, F, A>, Free<'a, G, B>),
+    {
+        let (mut interpreter, mut program) = (self, program);
+        loop {
+            match program {
+                Free::Pure(b) => return (interpreter.head, b),
+                Free::Free(command) => {
+                    (interpreter, program) = pairing(interpreter.tail(), *command);
+                }
+            }
+        }
+    }
+}
+
+impl<'a, F, A: Clone + 'a> Clone for Cofree<'a, F, A>
+where
+    F: Functor<'a> + 'a,
+{
+    fn clone(&self) -> Self {
+        Cofree {
+            head: self.head.clone(),
+            tail: self.tail.clone(),
+        }
+    }
+}
+
+impl<'a, F, A: 'a> Functor<'a> for Cofree<'a, F, A>
+where
+    F: FunctorWrapped<'a> + 'a,
+{
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Cofree<'a, F, B>;
+
+    fn fmap<G, B: 'a>(self, f: G) -> Self::Wrapped<B>
+    where
+        G: Fn(Self::Unwrapped) -> B + 'a,
+    {
+        Cofree::fmap_with(self, Rc::new(f))
+    }
+}
+
+impl<'a, F, A: 'a> Cofree<'a, F, A>
+where
+    F: FunctorWrapped<'a> + 'a,
+{
+    fn fmap_with<G, B: 'a>(self, f: Rc<G>) -> Cofree<'a, F, B>
+    where
+        G: Fn(A) -> B + 'a,
+    {
+        let tail = self.tail;
+        Cofree::new(f(self.head), move || {
+            let f = f.clone();
+            F::fmap_wrapped(tail(), move |child| child.fmap_with(f.clone()))
+        })
+    }
+
+    fn extend_with<G, B: 'a>(self, f: Rc<G>) -> Cofree<'a, F, B>
+    where
+        A: Clone,
+        G: Fn(Self) -> B + 'a,
+    {
+        // extend f w = f w :< fmap (extend f) (unwrap w)
+        let tail = self.tail.clone();
+        Cofree::new(f(self), move || {
+            let f = f.clone();
+            F::fmap_wrapped(tail(), move |child| child.extend_with(f.clone()))
+        })
+    }
+}
+
+impl<'a, F, A: Clone + 'a> Comonad<'a> for Cofree<'a, F, A>
+where
+    F: FunctorWrapped<'a> + 'a,
+{
+    fn extract(self) -> Self::Unwrapped {
+        self.head
+    }
+
+    fn extend<G, B: 'a>(self, f: G) -> Self::Wrapped<B>
+    where
+        G: Fn(Self) -> B + 'a,
+    {
+        self.extend_with(Rc::new(f))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use crate::{
+        Cofree, Comonad, Free, Functor, FunctorOnce, FunctorOnceWrapped, FunctorWrapped, Identity,
+        Monad,
+    };
+
+    #[derive(Clone)]
+    enum ExprF<A> {
+        Lit(i32),
+        Add(A, A),
+        Mul(A, A),
+    }
+
+    impl<'a, A> Functor<'a> for ExprF<A> {
+        type Unwrapped = A;
+
+        type Wrapped<B: 'a> = ExprF<B>;
+
+        fn fmap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+        where
+            F: Fn(Self::Unwrapped) -> B + 'a,
+        {
+            match self {
+                ExprF::Lit(n) => ExprF::Lit(n),
+                ExprF::Add(a, b) => ExprF::Add(f(a), f(b)),
+                ExprF::Mul(a, b) => ExprF::Mul(f(a), f(b)),
+            }
+        }
+    }
+
+    impl<'a, A> FunctorWrapped<'a> for ExprF<A> {
+        fn fmap_wrapped<X: 'a, Y: 'a, G>(fx: ExprF<X>, f: G) -> ExprF<Y>
+        where
+            G: Fn(X) -> Y + 'a,
+        {
+            fx.fmap(f)
+        }
+    }
+
+    /// A plain recursive AST
+    struct Expr(ExprF<Box<Expr>>);
+
+    fn lit(n: i32) -> Box<Expr> {
+        Box::new(Expr(ExprF::Lit(n)))
+    }
+
+    fn add(a: Box<Expr>, b: Box<Expr>) -> Box<Expr> {
+        Box::new(Expr(ExprF::Add(a, b)))
+    }
+
+    fn mul(a: Box<Expr>, b: Box<Expr>) -> Box<Expr> {
+        Box::new(Expr(ExprF::Mul(a, b)))
+    }
+
+    type Annotated<'a, A> = Cofree<'a, ExprF<()>, A>;
+
+    /// Annotate every node with its source code
+    fn annotate(expr: &Expr) -> Annotated<'_, String> {
+        fn show(expr: &Expr) -> String {
+            match &expr.0 {
+                ExprF::Lit(n) => n.to_string(),
+                ExprF::Add(a, b) => format!("({} + {})", show(a), show(b)),
+                ExprF::Mul(a, b) => format!("{} * {}", show(a), show(b)),
+            }
+        }
+
+        Cofree::unfold(expr, |e: &Expr| {
+            let children = match &e.0 {
+                ExprF::Lit(n) => ExprF::Lit(*n),
+                ExprF::Add(a, b) => ExprF::Add(a.as_ref(), b.as_ref()),
+                ExprF::Mul(a, b) => ExprF::Mul(a.as_ref(), b.as_ref()),
+            };
+            (show(e), children)
+        })
+    }
+
+    fn value(node: Annotated<String>) -> i32 {
+        match node.tail() {
+            ExprF::Lit(n) => n,
+            ExprF::Add(a, b) => value(a) + value(b),
+            ExprF::Mul(a, b) => value(a) * value(b),
+        }
+    }
+
+    fn children<A: Clone>(node: &Annotated<A>) -> Vec<A> {
+        match node.tail() {
+            ExprF::Lit(_) => vec![],
+            ExprF::Add(a, b) | ExprF::Mul(a, b) => vec![a.extract(), b.extract()],
+        }
+    }
+
+    #[test]
+    fn cofree_annotate_ast() {
+        let expr = mul(add(lit(1), lit(2)), lit(14));
+        let annotated = annotate(&expr);
+
+        assert_eq!(annotated.head(), "(1 + 2) * 14");
+        assert_eq!(children(&annotated), vec!["(1 + 2)", "14"]);
+
+        // Every node gets its value, next to its source
+        let values = annotated.extend(|node| format!("{} = {}", node.head(), value(node.clone())));
+        assert_eq!(values.head(), "(1 + 2) * 14 = 42");
+        assert_eq!(children(&values), vec!["(1 + 2) = 3", "14 = 14"]);
+
+        let lengths = values.fmap(|s| s.len());
+        assert_eq!(children(&lengths), vec![11, 7]);
+    }
+
+    #[test]
+    fn cofree_coiter_stream() {
+        let naturals = Cofree::<Identity<()>, u64>::coiter(0, |n| Identity::new(n + 1));
+
+        let take = |stream: Cofree<'_, Identity<()>, u64>, n: usize| {
+            let mut stream = stream;
+            let mut values = vec![];
+            for _ in 0..n {
+                values.push(*stream.head());
+                stream = stream.tail().execute();
+            }
+            values
+        };
+
+        assert_eq!(take(naturals.clone(), 5), vec![0, 1, 2, 3, 4]);
+
+        // Each value is replaced by the sum of itself and the next one
+        let sums = naturals.extend(|w| w.head() + w.tail().execute().head());
+        assert_eq!(take(sums, 5), vec![1, 3, 5, 7, 9]);
+    }
+
+    /// The instructions of a counter program
+    enum CounterF<'a, A> {
+        Incr(i32, A),
+        Get(Box<dyn 'a + FnOnce(i32) -> A>),
+    }
+
+    impl<'a, A: 'a> FunctorOnce<'a> for CounterF<'a, A> {
+        type Unwrapped = A;
+
+        type Wrapped<B: 'a> = CounterF<'a, B>;
+
+        fn fmap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+        where
+            F: FnOnce(Self::Unwrapped) -> B + 'a,
+        {
+            match self {
+                CounterF::Incr(n, next) => CounterF::Incr(n, f(next)),
+                CounterF::Get(cont) => CounterF::Get(Box::new(move |n| f(cont(n)))),
+            }
+        }
+    }
+
+    impl<'a, A: 'a> FunctorOnceWrapped<'a> for CounterF<'a, A> {
+        fn fmap_wrapped<X: 'a, Y: 'a, F>(fx: CounterF<'a, X>, f: F) -> CounterF<'a, Y>
+        where
+            F: FnOnce(X) -> Y + 'a,
+        {
+            fx.fmap(f)
+        }
+    }
+
+    /// The handlers of a counter interpreter: one for each instruction
+    #[derive(Clone)]
+    struct CoCounterF<'a, A> {
+        incr: Rc<dyn 'a + Fn(i32) -> A>,
+        get: (i32, A),
+    }
+
+    impl<'a, A: 'a> Functor<'a> for CoCounterF<'a, A> {
+        type Unwrapped = A;
+
+        type Wrapped<B: 'a> = CoCounterF<'a, B>;
+
+        fn fmap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+        where
+            F: Fn(Self::Unwrapped) -> B + 'a,
+        {
+            let f = Rc::new(f);
+            let (n, a) = self.get;
+            let incr = self.incr;
+            let g = f.clone();
+            CoCounterF {
+                incr: Rc::new(move |n| g(incr(n))),
+                get: (n, f(a)),
+            }
+        }
+    }
+
+    impl<'a, A: 'a> FunctorWrapped<'a> for CoCounterF<'a, A> {
+        fn fmap_wrapped<X: 'a, Y: 'a, G>(fx: CoCounterF<'a, X>, f: G) -> CoCounterF<'a, Y>
+        where
+            G: Fn(X) -> Y + 'a,
+        {
+            fx.fmap(f)
+        }
+    }
+
+    type Counter<'a, A> = Free<'a, CounterF<'a, ()>, A>;
+
+    fn incr<'a>(n: i32) -> Counter<'a, ()> {
+        Free::lift_f(CounterF::Incr(n, ()))
+    }
+
+    fn get<'a>() -> Counter<'a, i32> {
+        Free::lift_f(CounterF::Get(Box::new(|n| n)))
+    }
+
+    #[test]
+    fn cofree_pair_with_free() {
+        let interpreter: Cofree<CoCounterF<()>, i32> = Cofree::coiter(0, |state| CoCounterF {
+            incr: Rc::new(move |n| state + n),
+            get: (state, state),
+        });
+
+        let program = incr(40)
+            .bind(|_| get())
+            .bind(|n| incr(2).bind(move |_| Free::Pure(format!("was {}", n))));
+
+        let (state, result) = interpreter.pair(program, |handlers, command| match command {
+            CounterF::Incr(n, next) => ((handlers.incr)(n), next),
+            CounterF::Get(cont) => {
+                let (n, next) = handlers.get;
+                (next, cont(n))
+            }
+        });
+
+        assert_eq!(state, 42);
+        assert_eq!(result, "was 40");
+    }
+}
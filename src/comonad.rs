@@ -0,0 +1,85 @@
+use crate::Functor;
+
+/// `Comonad` is the dual of `Monad`: a monad puts values into a context and chains computations
+/// producing contexts, a comonad takes values out of a context and extends computations consuming contexts.
+///
+/// A comonadic value is a "focused" structure (a position in a grid, a cursor in a list, a value with its
+/// environment): `extract` reads the value under focus, while `extend` runs a computation on every possible
+/// focus of the structure, so that each one can look at its whole neighbourhood (e.g. cellular automata).
+///
+/// The `Fn(Self) -> B` passed to `extend` receives each refocused structure by value,
+/// so instances over owned data usually need some `Clone` bounds.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/comonad/docs/Control-Comonad.html>
+///
+/// ```haskell
+/// class Functor w => Comonad w where
+///   extract :: w a -> a
+///   extend :: (w a -> b) -> w a -> w b
+///   duplicate :: w a -> w (w a)
+/// ```
+pub trait Comonad<'a>: Functor<'a> {
+    /// Extract the value under focus.
+    fn extract(self) -> Self::Unwrapped;
+
+    /// Apply `f` to the structure focused on each of its positions, collecting the results in the same shape.
+    fn extend<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(Self) -> B + 'a,
+        Self: Sized;
+
+    /// Replace every value by the whole structure focused on it.
+    fn duplicate(self) -> Self::Wrapped<Self>
+    where
+        Self: Sized + 'a,
+    {
+        self.extend(|w| w)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Comonad;
+    use crate::Env;
+    use crate::Functor;
+    use crate::Identity;
+    use crate::NonEmpty;
+
+    #[test]
+    fn comonad_laws() {
+        // extend extract = id
+        let xs = NonEmpty::new(1, vec![2, 3]);
+        assert_eq!(xs.clone().extend(|w| w.extract()), xs);
+
+        // extract . extend f = f
+        let sum = |w: NonEmpty<i32>| w.into_iter().sum::<i32>();
+        assert_eq!(xs.clone().extend(sum).extract(), sum(xs.clone()));
+
+        // extend f . extend g = extend (f . extend g)
+        let len = |w: NonEmpty<i32>| w.len() as i32;
+        assert_eq!(
+            xs.clone().extend(sum).extend(len),
+            xs.extend(move |w| len(w.extend(sum)))
+        );
+    }
+
+    #[test]
+    fn comonad_duplicate() {
+        assert_eq!(
+            Identity::new(42).duplicate(),
+            Identity::new(Identity::new(42))
+        );
+
+        assert_eq!(
+            NonEmpty::new(1, vec![2, 3]).duplicate(),
+            NonEmpty::new(
+                NonEmpty::new(1, vec![2, 3]),
+                vec![NonEmpty::new(2, vec![3]), NonEmpty::new(3, vec![])]
+            )
+        );
+
+        let env = Env::new("config", 41).duplicate();
+        assert_eq!(*env.ask(), "config");
+        assert_eq!(env.extract().fmap(|a| a + 1).extract(), 42);
+    }
+}
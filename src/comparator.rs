@@ -0,0 +1,170 @@
+use std::{cmp::Ordering, convert::Infallible, rc::Rc};
+
+use crate::{Contravariant, Decidable, Divisible, Monoid, Semigroup};
+
+/// An ordering of the values of type A.
+///
+/// Comparators form a `Monoid` through `Ordering::then`: `mappend` breaks the ties of the first comparator
+/// with the second one (`mempty` considers all values equal), so that a multi-key sort reads as
+/// `by_name.mappend(by_age)`, with keys extracted by `contramap`.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/base/docs/Data-Functor-Contravariant.html>
+///
+/// ```haskell
+/// newtype Comparison a = Comparison { getComparison :: a -> a -> Ordering }
+/// ```
+#[allow(clippy::type_complexity)]
+pub struct Comparator<'a, A> {
+    runner: Rc<dyn 'a + Fn(&A, &A) -> Ordering>,
+}
+
+impl<'a, A: 'a> Comparator<'a, A> {
+    /// Constructs a new `Comparator` from its comparison function.
+    pub fn new<F>(runner: F) -> Self
+    where
+        F: Fn(&A, &A) -> Ordering + 'a,
+    {
+        Comparator {
+            runner: Rc::new(runner),
+        }
+    }
+
+    /// Compare two values.
+    pub fn compare(&self, a1: &A, a2: &A) -> Ordering {
+        (self.runner)(a1, a2)
+    }
+
+    /// Borrow the comparator as a plain function, e.g. for `slice::sort_by`.
+    pub fn as_fn(&self) -> impl Fn(&A, &A) -> Ordering + '_ {
+        |a1, a2| self.compare(a1, a2)
+    }
+
+    /// The opposite ordering.
+    pub fn reverse(self) -> Self {
+        Comparator::new(move |a1, a2| self.compare(a2, a1))
+    }
+}
+
+impl<'a, A: Ord + 'a> Comparator<'a, A> {
+    /// The natural ordering of A.
+    pub fn natural() -> Self {
+        Comparator::new(A::cmp)
+    }
+}
+
+impl<'a, A> Clone for Comparator<'a, A> {
+    fn clone(&self) -> Self {
+        Comparator {
+            runner: self.runner.clone(),
+        }
+    }
+}
+
+impl<'a, A: 'a> Semigroup for Comparator<'a, A> {
+    fn mappend(self, other: Self) -> Self {
+        Comparator::new(move |a1, a2| self.compare(a1, a2).then_with(|| other.compare(a1, a2)))
+    }
+}
+
+impl<'a, A: 'a> Monoid for Comparator<'a, A> {
+    fn mempty() -> Self {
+        Comparator::new(|_, _| Ordering::Equal)
+    }
+}
+
+impl<'a, A: 'a> Contravariant<'a> for Comparator<'a, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Comparator<'a, B>;
+
+    fn contramap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(&B) -> Self::Unwrapped + 'a,
+    {
+        Comparator::new(move |b1, b2| self.compare(&f(b1), &f(b2)))
+    }
+}
+
+impl<'a, A: 'a> Divisible<'a> for Comparator<'a, A> {
+    /// Compare the first parts, then the second ones.
+    fn divide<F, B: 'a, C: 'a>(f: F, fb: Comparator<'a, B>, fc: Comparator<'a, C>) -> Self
+    where
+        F: Fn(&A) -> (B, C) + 'a,
+    {
+        Comparator::new(move |a1, a2| {
+            let ((b1, c1), (b2, c2)) = (f(a1), f(a2));
+            fb.compare(&b1, &b2).then_with(|| fc.compare(&c1, &c2))
+        })
+    }
+
+    fn conquer() -> Self {
+        Comparator::mempty()
+    }
+}
+
+impl<'a, A: 'a> Decidable<'a> for Comparator<'a, A> {
+    /// Values of the first alternative come before the ones of the second alternative.
+    fn choose<F, B: 'a, C: 'a>(f: F, fb: Comparator<'a, B>, fc: Comparator<'a, C>) -> Self
+    where
+        F: Fn(&A) -> Result<B, C> + 'a,
+    {
+        Comparator::new(move |a1, a2| match (f(a1), f(a2)) {
+            (Ok(b1), Ok(b2)) => fb.compare(&b1, &b2),
+            (Err(c1), Err(c2)) => fc.compare(&c1, &c2),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+        })
+    }
+
+    fn lose<F>(f: F) -> Self
+    where
+        F: Fn(&A) -> Infallible + 'a,
+    {
+        Comparator::new(move |a, _| match f(a) {})
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cmp::Ordering;
+
+    use crate::Comparator;
+    use crate::Contravariant;
+    use crate::Divisible;
+    use crate::Monoid;
+    use crate::Semigroup;
+
+    #[test]
+    fn comparator_monoid() {
+        let by_len = Comparator::natural().contramap(|s: &&str| s.len());
+        let alphabetical = Comparator::natural();
+
+        let mut words = vec!["pear", "fig", "apple", "kiwi"];
+        words.sort_by(by_len.mappend(alphabetical.reverse()).as_fn());
+        assert_eq!(words, vec!["fig", "pear", "kiwi", "apple"]);
+
+        assert_eq!(Comparator::mempty().compare(&1, &2), Ordering::Equal);
+        assert_eq!(
+            Comparator::mempty()
+                .mappend(Comparator::natural())
+                .compare(&1, &2),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn comparator_divide() {
+        let version: Comparator<&str> = Divisible::divide(
+            |v: &&str| {
+                let (major, minor) = v.split_once('.').unwrap();
+                (major.parse::<u32>().unwrap(), minor.parse::<u32>().unwrap())
+            },
+            Comparator::natural(),
+            Comparator::natural(),
+        );
+
+        let mut versions = vec!["1.10", "0.9", "1.2"];
+        versions.sort_by(version.as_fn());
+        assert_eq!(versions, vec!["0.9", "1.2", "1.10"]);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{trans::Continuation, ContT};
+
+/// The continuation monad: a computation which, given the rest of the program (the continuation),
+/// produces the final result of type R.
+///
+/// Continuations make it possible to express early exit (`call_cc`), generators
+/// and CPS-transformed interpreters.
+///
+/// `Cont` is just a `ContT` whose final result is a plain value instead of an inner monad,
+/// since binding continuations never needs to look inside the result.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/transformers/docs/Control-Monad-Trans-Cont.html>
+///
+/// ```haskell
+/// type Cont r = ContT r Identity
+/// ```
+pub type Cont<'a, R, A> = ContT<'a, R, A>;
+
+impl<'a, R: 'a> Cont<'a, R, R> {
+    /// Run the computation with the identity function as the final continuation.
+    pub fn eval_cont(self) -> R {
+        self.run_cont(|a| a)
+    }
+}
+
+impl<'a, R: 'a, A: 'a> Cont<'a, R, A> {
+    /// Delimit the continuations captured by `shift` inside `inner`.
+    pub fn reset(inner: Cont<'a, A, A>) -> Self {
+        Cont::new(move |k| k(inner.eval_cont()))
+    }
+
+    /// Capture the continuation up to the nearest enclosing `reset` and pass it to `f`.
+    pub fn shift<F>(f: F) -> Self
+    where
+        F: FnOnce(Continuation<'a, A, R>) -> Cont<'a, R, R> + 'a,
+    {
+        Cont::new(move |k| f(k).eval_cont())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::m;
+    use crate::Cont;
+    use crate::Exit;
+    use crate::Functor;
+    use crate::Monad;
+
+    #[test]
+    fn cont_fmap() {
+        let c: Cont<String, i32> = Cont::new(|k| k(41));
+
+        assert_eq!(c.fmap(|a| a + 1).run_cont(|a| a.to_string()), "42");
+    }
+
+    #[test]
+    fn cont_bind() {
+        let c: Cont<i32, i32> = Cont::new(|k| k(1));
+
+        let c2 = c.bind(|a| Cont::new(move |k| k(a + 41)));
+
+        assert_eq!(c2.eval_cont(), 42);
+    }
+
+    #[test]
+    fn cont_call_cc_early_return() {
+        let trace = Rc::new(RefCell::new(vec![]));
+
+        fn sum_checked<'a>(
+            mut values: Vec<i32>,
+            exit: Exit<'a, String, String>,
+            trace: Rc<RefCell<Vec<i32>>>,
+        ) -> Cont<'a, String, i32> {
+            if values.is_empty() {
+                return Cont::new(|k| k(0));
+            }
+
+            let x = values.remove(0);
+            trace.borrow_mut().push(x);
+            if x < 0 {
+                exit.exit(format!("negative: {}", x))
+            } else {
+                m! {
+                    rest <- sum_checked(values, exit, trace);
+                    Cont::new(move |k| k(x + rest))
+                }
+            }
+        }
+
+        let sum_positives = |values: Vec<i32>, trace: Rc<RefCell<Vec<i32>>>| {
+            Cont::call_cc(move |exit| {
+                m! {
+                    sum <- sum_checked(values, exit, trace);
+                    Cont::new(move |k| k(format!("sum: {}", sum)))
+                }
+            })
+        };
+
+        assert_eq!(
+            sum_positives(vec![1, 2, 3], trace.clone()).eval_cont(),
+            "sum: 6"
+        );
+        assert_eq!(*trace.borrow(), vec![1, 2, 3]);
+
+        trace.borrow_mut().clear();
+        assert_eq!(
+            sum_positives(vec![1, -2, 3], trace.clone()).eval_cont(),
+            "negative: -2"
+        );
+        // The third value is never checked
+        assert_eq!(*trace.borrow(), vec![1, -2]);
+    }
+
+    #[test]
+    fn cont_shift_reset() {
+        let c: Cont<i32, i32> = Cont::reset(m! {
+            x <- Cont::shift(|k| Cont::new(move |k2| k2(k(10) * 2)));
+            Cont::new(move |k| k(x + 1))
+        });
+
+        assert_eq!(c.eval_cont(), 22);
+    }
+}
@@ -0,0 +1,88 @@
+/// A `Contravariant` functor is the dual of a `Functor`: instead of producing values it consumes them,
+/// like a predicate, a comparator or an encoder.
+///
+/// Where `fmap` post-processes the output of a functor, `contramap` pre-processes the input:
+/// given a way to turn a b into an a, something consuming a's becomes something consuming b's.
+/// Values are consumed by reference, so the same predicate or comparator can be run many times.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/base/docs/Data-Functor-Contravariant.html>
+///
+/// ```haskell
+/// class Contravariant f where
+///   contramap :: (a' -> a) -> f a -> f a'
+/// ```
+pub trait Contravariant<'a> {
+    /// The type of the consumed values
+    type Unwrapped;
+
+    /// Target of the contramap operation. Like `Self`, but consuming a different type of values.
+    type Wrapped<B: 'a>: Contravariant<'a, Unwrapped = B>;
+
+    /// Turn a consumer of `Self::Unwrapped` into a consumer of B, converting every B with `f` first.
+    fn contramap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(&B) -> Self::Unwrapped + 'a;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Comparator;
+    use crate::Contravariant;
+    use crate::Equivalence;
+    use crate::Predicate;
+    use crate::Semigroup;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Person {
+        name: &'static str,
+        age: u32,
+    }
+
+    fn people() -> Vec<Person> {
+        vec![
+            Person {
+                name: "Bob",
+                age: 42,
+            },
+            Person {
+                name: "Alice",
+                age: 42,
+            },
+            Person {
+                name: "Carol",
+                age: 7,
+            },
+        ]
+    }
+
+    #[test]
+    fn contravariant_multi_key_sort() {
+        let by_age = Comparator::natural().contramap(|p: &Person| p.age);
+        let by_name = Comparator::natural().contramap(|p: &Person| p.name);
+
+        let mut sorted = people();
+        sorted.sort_by(by_age.clone().mappend(by_name.clone()).as_fn());
+        assert_eq!(
+            sorted.iter().map(|p| p.name).collect::<Vec<_>>(),
+            vec!["Carol", "Alice", "Bob"]
+        );
+
+        sorted.sort_by(by_age.reverse().mappend(by_name).as_fn());
+        assert_eq!(
+            sorted.iter().map(|p| p.name).collect::<Vec<_>>(),
+            vec!["Alice", "Bob", "Carol"]
+        );
+    }
+
+    #[test]
+    fn contravariant_filter_and_group() {
+        let adult = Predicate::new(|age: &u32| *age >= 18).contramap(|p: &Person| p.age);
+        let adults: Vec<_> = people().into_iter().filter(adult.as_fn()).collect();
+        assert_eq!(adults.len(), 2);
+
+        let same_age = Equivalence::equal().contramap(|p: &Person| p.age);
+        let [bob, alice, carol] = <[Person; 3]>::try_from(people()).unwrap();
+        assert!(same_age.equivalent(&bob, &alice));
+        assert!(!same_age.equivalent(&alice, &carol));
+    }
+}
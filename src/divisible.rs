@@ -0,0 +1,165 @@
+use std::convert::Infallible;
+
+use crate::Contravariant;
+
+/// `Divisible` is the contravariant analogue of `Applicative`: it builds a consumer of a whole value
+/// from consumers of its parts.
+///
+/// `divide` splits every value into two parts and runs a consumer on each, combining what they do
+/// (encoders concatenate their outputs, predicates must both hold, comparators compare lexicographically);
+/// `conquer` is the consumer which does nothing, for values without parts.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/contravariant/docs/Data-Functor-Contravariant-Divisible.html>
+///
+/// ```haskell
+/// class Contravariant f => Divisible f where
+///   divide  :: (a -> (b, c)) -> f b -> f c -> f a
+///   conquer :: f a
+/// ```
+pub trait Divisible<'a>: Contravariant<'a> + Sized {
+    /// Consume a value by splitting it with `f`, then consuming both parts.
+    fn divide<F, B: 'a, C: 'a>(f: F, fb: Self::Wrapped<B>, fc: Self::Wrapped<C>) -> Self
+    where
+        F: Fn(&Self::Unwrapped) -> (B, C) + 'a;
+
+    /// The consumer which ignores its input.
+    fn conquer() -> Self;
+}
+
+/// `Decidable` is the contravariant analogue of `Alternative`: it builds a consumer of a sum type
+/// from consumers of its alternatives.
+///
+/// `choose` decides which alternative each value belongs to and runs the matching consumer,
+/// `lose` is the consumer of a type without values.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/contravariant/docs/Data-Functor-Contravariant-Divisible.html>
+///
+/// ```haskell
+/// class Divisible f => Decidable f where
+///   lose   :: (a -> Void) -> f a
+///   choose :: (a -> Either b c) -> f b -> f c -> f a
+/// ```
+pub trait Decidable<'a>: Divisible<'a> {
+    /// Consume a value by deciding with `f` which of the two consumers runs.
+    fn choose<F, B: 'a, C: 'a>(f: F, fb: Self::Wrapped<B>, fc: Self::Wrapped<C>) -> Self
+    where
+        F: Fn(&Self::Unwrapped) -> Result<B, C> + 'a;
+
+    /// Consume a type which has no values.
+    fn lose<F>(f: F) -> Self
+    where
+        F: Fn(&Self::Unwrapped) -> Infallible + 'a;
+}
+
+#[cfg(test)]
+mod test {
+    use std::cmp::Ordering;
+
+    use crate::Comparator;
+    use crate::Contravariant;
+    use crate::Decidable;
+    use crate::Divisible;
+    use crate::Op;
+
+    type Encoder<'a, A> = Op<'a, String, A>;
+
+    fn int<'a>() -> Encoder<'a, i32> {
+        Op::new(|n: &i32| n.to_string())
+    }
+
+    fn string<'a>() -> Encoder<'a, String> {
+        Op::new(|s: &String| format!("{:?}", s))
+    }
+
+    fn field<'a, A: 'a>(name: &'static str, value: Encoder<'a, A>) -> Encoder<'a, A> {
+        Op::new(move |a: &A| format!("{}={};", name, value.run(a)))
+    }
+
+    enum Shape {
+        Circle(i32),
+        Rect(i32, i32),
+    }
+
+    fn shape<'a>() -> Encoder<'a, Shape> {
+        let circle = field("r", int());
+        let rect = Divisible::divide(
+            |&(w, h): &(i32, i32)| (w, h),
+            field("w", int()),
+            field("h", int()),
+        );
+
+        Decidable::choose(
+            |s: &Shape| match s {
+                Shape::Circle(r) => Ok(*r),
+                Shape::Rect(w, h) => Err((*w, *h)),
+            },
+            circle,
+            rect,
+        )
+    }
+
+    #[test]
+    fn divisible_encoder() {
+        struct User {
+            name: String,
+            age: i32,
+        }
+
+        let user: Encoder<User> = Divisible::divide(
+            |u: &User| (u.name.clone(), u.age),
+            field("name", string()),
+            field("age", int()),
+        );
+
+        let alice = User {
+            name: "Alice".to_string(),
+            age: 42,
+        };
+        assert_eq!(user.run(&alice), "name=\"Alice\";age=42;");
+
+        let nothing: Encoder<User> = Divisible::conquer();
+        assert_eq!(nothing.run(&alice), "");
+    }
+
+    #[test]
+    fn decidable_encoder() {
+        let shape = shape();
+        assert_eq!(shape.run(&Shape::Circle(3)), "r=3;");
+        assert_eq!(shape.run(&Shape::Rect(4, 2)), "w=4;h=2;");
+
+        let sizes = shape.contramap(|&size: &i32| {
+            if size < 0 {
+                Shape::Circle(-size)
+            } else {
+                Shape::Rect(size, size)
+            }
+        });
+        assert_eq!(sizes.run(&-1), "r=1;");
+    }
+
+    #[test]
+    fn decidable_comparator() {
+        // Circles first, then rectangles by area
+        let shapes: Comparator<Shape> = Decidable::choose(
+            |s: &Shape| match s {
+                Shape::Circle(r) => Ok(*r),
+                Shape::Rect(w, h) => Err(w * h),
+            },
+            Comparator::natural(),
+            Comparator::natural(),
+        );
+
+        assert_eq!(
+            shapes.compare(&Shape::Circle(10), &Shape::Rect(1, 1)),
+            Ordering::Less
+        );
+        assert_eq!(
+            shapes.compare(&Shape::Rect(2, 3), &Shape::Rect(3, 2)),
+            Ordering::Equal
+        );
+        assert_eq!(
+            shapes.compare(&Shape::Circle(2), &Shape::Circle(1)),
+            Ordering::Greater
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{Comonad, Functor};
+
+/// `Env` (a.k.a. the co-reader) pairs a value with a read-only environment.
+///
+/// It is the comonadic dual of `Reader`: instead of a computation waiting for its environment,
+/// the environment travels with the value, and every computation run by `extend` can `ask` for it.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/comonad/docs/Control-Comonad-Env.html>
+///
+/// ```haskell
+/// data Env e a = Env e a
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Env<E, A> {
+    env: E,
+    value: A,
+}
+
+impl<E, A> Env<E, A> {
+    /// Pair a value with its environment.
+    pub fn new(env: E, value: A) -> Self {
+        Env { env, value }
+    }
+
+    /// Retrieve the environment.
+    pub fn ask(&self) -> &E {
+        &self.env
+    }
+
+    /// Retrieve a value which is a function of the environment.
+    pub fn asks<F, B>(&self, f: F) -> B
+    where
+        F: FnOnce(&E) -> B,
+    {
+        f(&self.env)
+    }
+
+    /// Change the environment, keeping the value.
+    pub fn local<F, E2>(self, f: F) -> Env<E2, A>
+    where
+        F: FnOnce(E) -> E2,
+    {
+        Env::new(f(self.env), self.value)
+    }
+}
+
+impl<'a, E, A> Functor<'a> for Env<E, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Env<E, B>;
+
+    fn fmap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(Self::Unwrapped) -> B + 'a,
+    {
+        Env::new(self.env, f(self.value))
+    }
+}
+
+impl<'a, E: Clone, A> Comonad<'a> for Env<E, A> {
+    fn extract(self) -> Self::Unwrapped {
+        self.value
+    }
+
+    fn extend<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(Self) -> B + 'a,
+    {
+        Env::new(self.env.clone(), f(self))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Comonad;
+    use crate::Env;
+    use crate::Functor;
+
+    #[derive(Clone)]
+    struct Config {
+        currency: &'static str,
+        rate: f64,
+    }
+
+    #[test]
+    fn env_ask_local() {
+        let price = Env::new(2, 21);
+        assert_eq!(*price.ask(), 2);
+        assert_eq!(price.asks(|e| e * 10), 20);
+        assert_eq!(price.local(|e| e + 1).ask(), &3);
+        assert_eq!(price.fmap(|p| p * 2).extract(), 42);
+    }
+
+    #[test]
+    fn env_extend() {
+        let config = Config {
+            currency: "EUR",
+            rate: 0.5,
+        };
+
+        let label = Env::new(config, 84.0)
+            .extend(|w| w.ask().rate * w.extract())
+            .extend(|w| format!("{} {}", w.clone().extract(), w.ask().currency));
+
+        assert_eq!(label.extract(), "42 EUR");
+    }
+}
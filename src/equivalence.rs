@@ -0,0 +1,147 @@
+use std::{convert::Infallible, rc::Rc};
+
+use crate::{Contravariant, Decidable, Divisible, Monoid, Semigroup};
+
+/// An equivalence relation on the values of type A: which values should be considered the same.
+///
+/// Equivalences combine with `Semigroup`/`Monoid` as a conjunction (`mempty` relates all values),
+/// and `contramap` compares values through a key, e.g. `Equivalence::equal().contramap(|p: &Person| p.age)`.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/base/docs/Data-Functor-Contravariant.html>
+///
+/// ```haskell
+/// newtype Equivalence a = Equivalence { getEquivalence :: a -> a -> Bool }
+/// ```
+#[allow(clippy::type_complexity)]
+pub struct Equivalence<'a, A> {
+    runner: Rc<dyn 'a + Fn(&A, &A) -> bool>,
+}
+
+impl<'a, A: 'a> Equivalence<'a, A> {
+    /// Constructs a new `Equivalence` from the relation.
+    pub fn new<F>(runner: F) -> Self
+    where
+        F: Fn(&A, &A) -> bool + 'a,
+    {
+        Equivalence {
+            runner: Rc::new(runner),
+        }
+    }
+
+    /// Check whether two values are equivalent.
+    pub fn equivalent(&self, a1: &A, a2: &A) -> bool {
+        (self.runner)(a1, a2)
+    }
+}
+
+impl<'a, A: PartialEq + 'a> Equivalence<'a, A> {
+    /// Equality, as defined by `PartialEq`.
+    pub fn equal() -> Self {
+        Equivalence::new(A::eq)
+    }
+}
+
+impl<'a, A> Clone for Equivalence<'a, A> {
+    fn clone(&self) -> Self {
+        Equivalence {
+            runner: self.runner.clone(),
+        }
+    }
+}
+
+impl<'a, A: 'a> Semigroup for Equivalence<'a, A> {
+    fn mappend(self, other: Self) -> Self {
+        Equivalence::new(move |a1, a2| self.equivalent(a1, a2) && other.equivalent(a1, a2))
+    }
+}
+
+impl<'a, A: 'a> Monoid for Equivalence<'a, A> {
+    fn mempty() -> Self {
+        Equivalence::new(|_, _| true)
+    }
+}
+
+impl<'a, A: 'a> Contravariant<'a> for Equivalence<'a, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Equivalence<'a, B>;
+
+    fn contramap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(&B) -> Self::Unwrapped + 'a,
+    {
+        Equivalence::new(move |b1, b2| self.equivalent(&f(b1), &f(b2)))
+    }
+}
+
+impl<'a, A: 'a> Divisible<'a> for Equivalence<'a, A> {
+    fn divide<F, B: 'a, C: 'a>(f: F, fb: Equivalence<'a, B>, fc: Equivalence<'a, C>) -> Self
+    where
+        F: Fn(&A) -> (B, C) + 'a,
+    {
+        Equivalence::new(move |a1, a2| {
+            let ((b1, c1), (b2, c2)) = (f(a1), f(a2));
+            fb.equivalent(&b1, &b2) && fc.equivalent(&c1, &c2)
+        })
+    }
+
+    fn conquer() -> Self {
+        Equivalence::mempty()
+    }
+}
+
+impl<'a, A: 'a> Decidable<'a> for Equivalence<'a, A> {
+    /// Values are only equivalent when they belong to the same alternative.
+    fn choose<F, B: 'a, C: 'a>(f: F, fb: Equivalence<'a, B>, fc: Equivalence<'a, C>) -> Self
+    where
+        F: Fn(&A) -> Result<B, C> + 'a,
+    {
+        Equivalence::new(move |a1, a2| match (f(a1), f(a2)) {
+            (Ok(b1), Ok(b2)) => fb.equivalent(&b1, &b2),
+            (Err(c1), Err(c2)) => fc.equivalent(&c1, &c2),
+            _ => false,
+        })
+    }
+
+    fn lose<F>(f: F) -> Self
+    where
+        F: Fn(&A) -> Infallible + 'a,
+    {
+        Equivalence::new(move |a, _| match f(a) {})
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Contravariant;
+    use crate::Decidable;
+    use crate::Equivalence;
+    use crate::Semigroup;
+
+    #[test]
+    fn equivalence_contramap_mappend() {
+        let same_parity = Equivalence::equal().contramap(|n: &i32| n % 2);
+        let same_sign = Equivalence::equal().contramap(|n: &i32| n.signum());
+
+        assert!(same_parity.equivalent(&2, &-4));
+        assert!(!same_parity.equivalent(&2, &3));
+
+        let both = same_parity.mappend(same_sign);
+        assert!(both.equivalent(&2, &4));
+        assert!(!both.equivalent(&2, &-4));
+    }
+
+    #[test]
+    fn equivalence_choose() {
+        let case_insensitive = Equivalence::equal().contramap(|s: &String| s.to_lowercase());
+        let parsed: Equivalence<&str> = Decidable::choose(
+            |s: &&str| s.parse::<i32>().map_err(|_| s.to_string()),
+            Equivalence::equal(),
+            case_insensitive,
+        );
+
+        assert!(parsed.equivalent(&"042", &"42"));
+        assert!(parsed.equivalent(&"Yes", &"yES"));
+        assert!(!parsed.equivalent(&"1", &"one"));
+    }
+}
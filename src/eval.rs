@@ -0,0 +1,218 @@
+use std::{cell::Cell, ops::ControlFlow, rc::Rc};
+
+use crate::{Applicative, Apply, Functor, Monad, MonadRec};
+
+/// One step of an `Eval` computation: either it is finished or there is some more work to do.
+pub(crate) enum Bounce<'a> {
+    Done,
+    More(Box<dyn 'a + FnOnce() -> Bounce<'a>>),
+}
+
+impl<'a> Bounce<'a> {
+    pub(crate) fn more<F>(f: F) -> Self
+    where
+        F: FnOnce() -> Bounce<'a> + 'a,
+    {
+        Bounce::More(Box::new(f))
+    }
+
+    /// Keep bouncing until the computation is done: the loop is what keeps the stack flat.
+    pub(crate) fn run(self) {
+        let mut bounce = self;
+        while let Bounce::More(next) = bounce {
+            bounce = next();
+        }
+    }
+}
+
+type Continuation<'a, A> = Box<dyn 'a + FnOnce(A) -> Bounce<'a>>;
+
+/// `Eval` (a.k.a. `Trampoline`) is a monad which controls evaluation,
+/// making deeply nested or recursive computations stack-safe.
+///
+/// Instead of calling each other, the steps of an `Eval` computation return
+/// the rest of the work to the caller, and `run` executes them one after the other
+/// in an explicit loop on the heap. A chain of a million `bind`s runs in constant stack space.
+///
+/// Inspired by Scala [`Cats Eval`](https://typelevel.org/cats/datatypes/eval.html).
+pub struct Eval<'a, A> {
+    runner: Box<dyn 'a + FnOnce(Continuation<'a, A>) -> Bounce<'a>>,
+}
+
+impl<'a, A: 'a> Eval<'a, A> {
+    fn new<F>(runner: F) -> Self
+    where
+        F: FnOnce(Continuation<'a, A>) -> Bounce<'a> + 'a,
+    {
+        Self {
+            runner: Box::new(runner),
+        }
+    }
+
+    /// An already computed value.
+    pub fn now(value: A) -> Self {
+        Eval::new(move |k| k(value))
+    }
+
+    /// A value computed lazily, when the `Eval` is run.
+    pub fn later<F>(f: F) -> Self
+    where
+        F: FnOnce() -> A + 'a,
+    {
+        Eval::new(move |k| Bounce::more(move || k(f())))
+    }
+
+    /// Build the `Eval` computation lazily, when it is run.
+    ///
+    /// Useful to write stack-safe recursive functions.
+    pub fn defer<F>(f: F) -> Self
+    where
+        F: FnOnce() -> Eval<'a, A> + 'a,
+    {
+        Eval::new(move |k| Bounce::more(move || (f().runner)(k)))
+    }
+
+    /// Run the computation and extract the result.
+    pub fn run(self) -> A {
+        let result = Rc::new(Cell::new(None));
+        let slot = result.clone();
+
+        (self.runner)(Box::new(move |a| {
+            slot.set(Some(a));
+            Bounce::Done
+        }))
+        .run();
+
+        result
+            .take()
+            .expect("the final continuation is always called")
+    }
+}
+
+impl<'a, A: 'a> Functor<'a> for Eval<'a, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Eval<'a, B>;
+
+    fn fmap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> B + 'a,
+    {
+        Eval::new(move |k| {
+            Bounce::more(move || (self.runner)(Box::new(move |a| Bounce::more(move || k(f(a))))))
+        })
+    }
+}
+
+impl<'a, A: 'a> Apply<'a> for Eval<'a, A> {
+    fn ap<F, B: 'a>(self, f: Self::Wrapped<F>) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> B + 'a,
+    {
+        self.lift_a2(f, |a, f| f(a))
+    }
+
+    fn lift_a2<F, B: 'a, C: 'a>(self, b: Self::Wrapped<B>, f: F) -> Self::Wrapped<C>
+    where
+        F: FnOnce(Self::Unwrapped, B) -> C + 'a,
+    {
+        self.bind(move |a1| b.bind(move |a2| Eval::now(f(a1, a2))))
+    }
+}
+
+impl<'a, A: 'a> Applicative<'a> for Eval<'a, A> {
+    fn of(value: Self::Unwrapped) -> Self::Wrapped<Self::Unwrapped> {
+        Eval::now(value)
+    }
+}
+
+impl<'a, A: 'a> Monad<'a> for Eval<'a, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Eval<'a, B>;
+
+    fn bind<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> Self::Wrapped<B> + 'a,
+    {
+        Eval::new(move |k| {
+            Bounce::more(move || {
+                (self.runner)(Box::new(move |a| Bounce::more(move || (f(a).runner)(k))))
+            })
+        })
+    }
+
+    fn of<T: 'a>(value: T) -> Self::Wrapped<T> {
+        Eval::now(value)
+    }
+}
+
+impl<'a, A: 'a> MonadRec<'a> for Eval<'a, A> {
+    fn tail_rec_m<B: 'a, C: 'a, F>(init: B, mut f: F) -> Self::Wrapped<C>
+    where
+        F: FnMut(B) -> Self::Wrapped<ControlFlow<C, B>> + 'a,
+    {
+        // Recursion is fine here: `bind` hands every step back to the `run` loop
+        f(init).bind(move |step| match step {
+            ControlFlow::Continue(b) => Self::tail_rec_m(b, f),
+            ControlFlow::Break(c) => Eval::now(c),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::m;
+    use crate::Apply;
+    use crate::Eval;
+    use crate::Functor;
+    use crate::Monad;
+
+    #[test]
+    fn eval_now_later_defer() {
+        assert_eq!(Eval::now(42).run(), 42);
+        assert_eq!(Eval::later(|| 40 + 2).run(), 42);
+        assert_eq!(Eval::defer(|| Eval::now(42)).run(), 42);
+    }
+
+    #[test]
+    fn eval_fmap_lifta2() {
+        let e = Eval::later(|| 20).fmap(|a| a + 1);
+
+        assert_eq!(e.lift_a2(Eval::now(2), |a, b| a * b).run(), 42);
+    }
+
+    #[test]
+    fn eval_macro() {
+        let e = m! {
+            a <- Eval::now(40);
+            b <- Eval::later(|| 2);
+            Eval::now(a + b)
+        };
+
+        assert_eq!(e.run(), 42);
+    }
+
+    #[test]
+    fn eval_million_left_binds() {
+        let mut e = Eval::now(0u64);
+        for _ in 0..1_000_000 {
+            e = e.bind(|a| Eval::now(a + 1));
+        }
+
+        assert_eq!(e.run(), 1_000_000);
+    }
+
+    #[test]
+    fn eval_deep_recursion() {
+        fn sum<'a>(n: u64) -> Eval<'a, u64> {
+            if n == 0 {
+                Eval::now(0)
+            } else {
+                Eval::defer(move || sum(n - 1)).fmap(move |s| s + n)
+            }
+        }
+
+        assert_eq!(sum(1_000_000).run(), 500_000_500_000);
+    }
+}
@@ -1,4 +1,13 @@
-use crate::{FunctorOnce, Monad};
+use std::{
+    cell::{Cell, RefCell},
+    ops::ControlFlow,
+    rc::Rc,
+};
+
+use crate::{
+    Applicative, Apply, Functor, FunctorOnce, FunctorOnceWrapped, Monad, MonadRec,
+    NaturalTransformation,
+};
 
 /// A free monad is a construction which allows you to build a `Monad` from any `Functor`.
 /// Like other monads, it is a pure way to represent and manipulate computations.
@@ -9,6 +18,9 @@ use crate::{FunctorOnce, Monad};
 /// * build an embedded DSL (domain-specific language)
 /// * run a computation using multiple different interpreters
 ///
+/// A program is usually run with `fold_map`, which interprets every instruction into a target monad
+/// by means of a `NaturalTransformation`.
+///
 /// Haskell definition from <https://hackage.haskell.org/package/free-5.2/docs/Control-Monad-Free.html>
 ///
 /// ```haskell
@@ -24,12 +36,135 @@ where
     Free(Box<F::Wrapped<Free<'a, F, A>>>),
 }
 
+impl<'a, F, A> Free<'a, F, A>
+where
+    F: FunctorOnceWrapped<'a> + 'a,
+{
+    /// Lift a single instruction into a program which runs it and returns its result.
+    ///
+    /// F can be any instance of the instruction functor: programs are usually written against
+    /// a fixed one, e.g. `Free<'a, KeyValF<'a, ()>, A>`, so they can be bound together.
+    pub fn lift_f(command: F::Wrapped<A>) -> Self {
+        // Free (fmap Pure command)
+        Free::Free(Box::new(F::fmap_wrapped(command, Free::Pure)))
+    }
+
+    /// Interpret the program into the monad G, translating every instruction with `nt`.
+    ///
+    /// The program is run by `tail_rec_m`, one instruction at a time:
+    /// as long as G is stack-safe, so is the interpreter, whatever the length of the program.
+    ///
+    /// This is why G has to be a `MonadRec`: `Option`, `Result`, `Identity`, `Eval`, `State`, `Writer`,
+    /// and `StateT` over `Option` or `Result`. To interpret into another monad, first interpret into `Eval`
+    /// (or `StateT` over it) and then map the result.
+    #[allow(clippy::type_complexity)]
+    pub fn fold_map<G, N>(self, mut nt: N) -> G::Wrapped<A>
+    where
+        G: MonadRec<'a>,
+        N: NaturalTransformation<'a, F, G> + 'a,
+        N::Target<Free<'a, F, A>>: Monad<
+            'a,
+            Unwrapped = Free<'a, F, A>,
+            Wrapped<ControlFlow<A, Free<'a, F, A>>> = G::Wrapped<ControlFlow<A, Free<'a, F, A>>>,
+        >,
+    {
+        G::tail_rec_m(self, move |program| match program {
+            Free::Pure(a) => G::of(ControlFlow::Break(a)),
+            Free::Free(command) => nt
+                .transform(*command)
+                .bind::<_, ControlFlow<A, Free<'a, F, A>>>(|next| {
+                    G::of(ControlFlow::Continue(next))
+                }),
+        })
+    }
+
+    /// Change the instruction functor of the program from F to G, translating every instruction with `nt`.
+    ///
+    /// The instructions holding the rest of the program as a plain value are translated in a loop,
+    /// the ones holding a continuation only when it is called: hoisting never grows the stack,
+    /// whatever the length of the program.
+    pub fn hoist<G, N>(self, nt: N) -> Free<'a, G, A>
+    where
+        G: FunctorOnceWrapped<'a> + 'a,
+        N: NaturalTransformation<'a, F, G, Target<Free<'a, F, A>> = G::Wrapped<Free<'a, F, A>>>
+            + 'a,
+    {
+        self.hoist_shared(Rc::new(RefCell::new(nt)))
+    }
+
+    fn hoist_shared<G, N>(self, nt: Rc<RefCell<N>>) -> Free<'a, G, A>
+    where
+        G: FunctorOnceWrapped<'a> + 'a,
+        N: NaturalTransformation<'a, F, G, Target<Free<'a, F, A>> = G::Wrapped<Free<'a, F, A>>>
+            + 'a,
+    {
+        // Walk down the program, translating every instruction whose rest is passed to fmap right away
+        let mut walked = vec![];
+        let mut program = self;
+        let mut hoisted = loop {
+            let command = match program {
+                Free::Pure(a) => break Free::Pure(a),
+                Free::Free(command) => nt.borrow_mut().transform(*command),
+            };
+
+            let next = Rc::new(Cell::new(None));
+            let walking = Rc::new(Cell::new(true));
+            let shape = {
+                let (next, walking, nt) = (next.clone(), walking.clone(), nt.clone());
+                G::fmap_wrapped(command, move |rest: Free<'a, F, A>| {
+                    if walking.get() {
+                        next.set(Some(rest));
+                        Hoisted::Walked
+                    } else {
+                        Hoisted::Later(rest.hoist_shared(nt))
+                    }
+                })
+            };
+            walking.set(false);
+
+            match next.take() {
+                Some(rest) => {
+                    walked.push(shape);
+                    program = rest;
+                }
+                // The rest is behind a continuation: it is hoisted when the continuation is called
+                None => {
+                    break Free::Free(Box::new(G::fmap_wrapped(shape, |rest| match rest {
+                        Hoisted::Later(rest) => rest,
+                        Hoisted::Walked => unreachable!("the rest of the program was not walked"),
+                    })))
+                }
+            }
+        };
+
+        // Then rebuild it from the bottom up
+        while let Some(shape) = walked.pop() {
+            hoisted = Free::Free(Box::new(G::fmap_wrapped(shape, move |rest| match rest {
+                Hoisted::Walked => hoisted,
+                Hoisted::Later(rest) => rest,
+            })));
+        }
+        hoisted
+    }
+}
+
+/// The rest of a program being hoisted, after its instruction has been translated
+enum Hoisted<'a, G, A: 'a>
+where
+    G: FunctorOnce<'a> + 'a,
+{
+    /// Passed to fmap right away, it is hoisted by the loop
+    Walked,
+    /// Passed to a continuation, it is hoisted when the continuation is called
+    Later(Free<'a, G, A>),
+}
+
 impl<'a, F, A> FunctorOnce<'a> for Free<'a, F, A>
 where
-    F: FunctorOnce<'a> + 'a,
+    F: FunctorOnceWrapped<'a> + 'a,
 {
     type Unwrapped = A;
-    type Wrapped<B: 'a> = Free<'a, F::Wrapped<Free<'a, F, A>>, B>;
+    type Wrapped<B: 'a> = Free<'a, F, B>;
 
     fn fmap<G, B: 'a>(self, f: G) -> Self::Wrapped<B>
     where
@@ -39,18 +174,63 @@ where
             Free::Pure(a) => Free::Pure(f(a)),
             Free::Free(b) => {
                 // Free (fmap g <$> fx)
-                Free::Free(Box::new((*b).fmap(move |a| a.fmap(f))))
+                Free::Free(Box::new(F::fmap_wrapped(*b, move |a| {
+                    FunctorOnce::fmap(a, f)
+                })))
             }
         }
     }
 }
 
+impl<'a, F, A> Functor<'a> for Free<'a, F, A>
+where
+    F: FunctorOnceWrapped<'a> + 'a,
+{
+    type Unwrapped = A;
+    type Wrapped<B: 'a> = Free<'a, F, B>;
+
+    fn fmap<G, B: 'a>(self, f: G) -> Self::Wrapped<B>
+    where
+        G: Fn(Self::Unwrapped) -> B + 'a,
+    {
+        FunctorOnce::fmap(self, f)
+    }
+}
+
+impl<'a, F, A> Apply<'a> for Free<'a, F, A>
+where
+    F: FunctorOnceWrapped<'a> + 'a,
+{
+    fn ap<G, B: 'a>(self, f: Self::Wrapped<G>) -> Self::Wrapped<B>
+    where
+        G: FnOnce(Self::Unwrapped) -> B + 'a,
+    {
+        self.bind(move |a| FunctorOnce::fmap(f, move |g| g(a)))
+    }
+
+    fn lift_a2<G, B: 'a, C: 'a>(self, b: Self::Wrapped<B>, f: G) -> Self::Wrapped<C>
+    where
+        G: FnOnce(Self::Unwrapped, B) -> C + 'a,
+    {
+        self.bind(move |a1| FunctorOnce::fmap(b, move |a2| f(a1, a2)))
+    }
+}
+
+impl<'a, F, A> Applicative<'a> for Free<'a, F, A>
+where
+    F: FunctorOnceWrapped<'a> + 'a,
+{
+    fn of(value: Self::Unwrapped) -> Self::Wrapped<Self::Unwrapped> {
+        Free::Pure(value)
+    }
+}
+
 impl<'a, F, A> Monad<'a> for Free<'a, F, A>
 where
-    F: FunctorOnce<'a> + 'a,
+    F: FunctorOnceWrapped<'a> + 'a,
 {
     type Unwrapped = A;
-    type Wrapped<T: 'a> = Free<'a, F::Wrapped<Free<'a, F, A>>, T>;
+    type Wrapped<T: 'a> = Free<'a, F, T>;
 
     fn bind<E, B: 'a>(self, f: E) -> Self::Wrapped<B>
     where
@@ -60,7 +240,7 @@ where
         // Free m >>= f = Free ((>>= f) <$> m)
         match self {
             Free::Pure(a) => f(a),
-            Free::Free(m) => Free::Free(Box::new((*m).fmap(|a| a.bind(f)))),
+            Free::Free(m) => Free::Free(Box::new(F::fmap_wrapped(*m, |a| a.bind(f)))),
         }
     }
 
@@ -69,31 +249,22 @@ where
     }
 }
 
-#[allow(unused)]
-pub fn lift_f<'a, F, A>(command: F) -> Free<'a, F, A>
-where
-    F: FunctorOnce<'a, Unwrapped = A>,
-{
-    // Free (fmap Pure command)
-    Free::Free(Box::new(command.fmap(|a| Free::Pure(a))))
-}
-
 #[cfg(test)]
 mod test {
 
     use std::{collections::HashMap, fmt::Display};
 
-    use crate::{m, Free, Free::Pure, Monad};
-
-    use super::{lift_f, FunctorOnce};
+    use crate::{
+        m, Applicative, Apply, Free, Free::Pure, FunctorOnce, FunctorOnceWrapped, Monad,
+        NaturalTransformation, State, StateT, Writer,
+    };
 
     pub enum KeyValF<'a, A> {
         Get(String, Box<dyn 'a + FnOnce(String) -> A>),
         Put(String, String, A),
     }
 
-    type KeyVal<'a, A> = Free<'a, KeyValF<'a, A>, A>;
-    type KeyValProg<'a, A> = Free<'a, KeyValF<'a, Free<'a, KeyValF<'a, A>, A>>, A>;
+    type KeyVal<'a, A> = Free<'a, KeyValF<'a, ()>, A>;
 
     impl<'a, A: 'a> FunctorOnce<'a> for KeyValF<'a, A> {
         type Unwrapped = A;
@@ -111,9 +282,18 @@ mod test {
         }
     }
 
+    impl<'a, A: 'a> FunctorOnceWrapped<'a> for KeyValF<'a, A> {
+        fn fmap_wrapped<X: 'a, Y: 'a, F>(fx: KeyValF<'a, X>, f: F) -> KeyValF<'a, Y>
+        where
+            F: FnOnce(X) -> Y + 'a,
+        {
+            fx.fmap(f)
+        }
+    }
+
     #[test]
     fn key_val_fmap() {
-        let get_key_f = |s| lift_f(KeyValF::Get(s, Box::new(|a| a)));
+        let get_key_f = |s| KeyVal::lift_f(KeyValF::Get(s, Box::new(|a| a)));
 
         let mut get_key_1 = get_key_f("1".to_owned());
 
@@ -150,7 +330,7 @@ mod test {
         }
     }
 
-    fn prog<'a>() -> KeyValProg<'a, String> {
+    fn prog<'a>() -> KeyVal<'a, String> {
         m! {
             put_key("1", "ue");
             put_key("2", "my love");
@@ -159,6 +339,50 @@ mod test {
         }
     }
 
+    /// A reusable helper: programs of any result type have the same type, so they can be composed freely
+    fn put_all<'a>(pairs: Vec<(&str, &str)>) -> KeyVal<'a, ()> {
+        let steps: Vec<KeyVal<()>> = pairs.into_iter().map(|(k, v)| put_key(k, v)).collect();
+
+        steps
+            .into_iter()
+            .rev()
+            .fold(Pure(()), |rest, step| step.bind(move |_| rest))
+    }
+
+    #[test]
+    fn key_val_type_stable_programs() {
+        let program: KeyVal<String> = m! {
+            put_all(vec![("1", "ue"), ("2", "my love")]);
+            a <- get_key("1");
+            b <- get_key("2");
+            Pure(format!("{} {}", a, b))
+        };
+
+        assert_eq!(
+            "Put 1,ue\nPut 2,my love\nGet 1\nGet 2\nreturn 1 2",
+            eval_to_string(program)
+        );
+    }
+
+    #[test]
+    fn key_val_apply_applicative() {
+        let both = || get_key("1").lift_a2(get_key("2"), |a, b| format!("{}, {}", a, b));
+
+        assert_eq!("Get 1\nGet 2\nreturn 1, 2", eval_to_string(both()));
+
+        let program = put_all(vec![("1", "a"), ("2", "b")]).bind(move |_| both());
+        assert_eq!("a, b", eval_real(program, &mut HashMap::<String, _>::new()));
+
+        let length = get_key("1").ap(<KeyVal<_> as Applicative>::of(|s: String| s.len()));
+        assert_eq!(
+            1,
+            eval_real(
+                put_key("1", "a").bind(|_| length),
+                &mut HashMap::<String, _>::new()
+            )
+        );
+    }
+
     #[test]
     fn key_val_bind_and_eval() {
         // We have a computation that still has to execute! We can interpret as we want!
@@ -176,14 +400,14 @@ mod test {
     }
 
     fn get_key<'a>(key: &str) -> KeyVal<'a, String> {
-        lift_f(KeyValF::Get(key.into(), Box::new(|a| a)))
+        Free::lift_f(KeyValF::Get(key.into(), Box::new(|a| a)))
     }
 
-    fn put_key<'a>(key: &str, val: &str) -> KeyVal<'a, String> {
-        lift_f(KeyValF::Put(key.into(), val.into(), String::new()))
+    fn put_key<'a>(key: &str, val: &str) -> KeyVal<'a, ()> {
+        Free::lift_f(KeyValF::Put(key.into(), val.into(), ()))
     }
 
-    fn eval_to_string<'a, A, R>(prog: Free<'a, KeyValF<'a, A>, R>) -> String
+    fn eval_to_string<R>(prog: KeyVal<R>) -> String
     where
         R: Display,
     {
@@ -196,10 +420,7 @@ mod test {
         }
     }
 
-    fn eval_real<'a, A, R>(
-        prog: Free<'a, KeyValF<'a, A>, R>,
-        cache: &mut HashMap<String, String>,
-    ) -> R
+    fn eval_real<R>(prog: KeyVal<R>, cache: &mut HashMap<String, String>) -> R
     where
         R: Display,
     {
@@ -217,4 +438,176 @@ mod test {
             },
         }
     }
+
+    type Store = HashMap<String, String>;
+
+    /// Runs the instructions against an in-memory store
+    struct InMemory;
+
+    impl<'a> NaturalTransformation<'a, KeyValF<'a, ()>, State<'a, Store, ()>> for InMemory {
+        type Target<X: 'a> = State<'a, Store, X>;
+
+        fn transform<X: 'a>(&mut self, fx: KeyValF<'a, X>) -> State<'a, Store, X> {
+            match fx {
+                KeyValF::Get(k, cont) => State::new(move |s: Store| {
+                    let v = s.get(&k).cloned().unwrap_or_default();
+                    (cont(v), s)
+                }),
+                KeyValF::Put(k, v, next) => State::new(move |mut s: Store| {
+                    s.insert(k, v);
+                    (next, s)
+                }),
+            }
+        }
+    }
+
+    /// Like `InMemory`, but reading a missing key fails
+    struct Strict;
+
+    impl<'a> NaturalTransformation<'a, KeyValF<'a, ()>, StateT<'a, Store, Option<((), Store)>>>
+        for Strict
+    {
+        type Target<X: 'a> = StateT<'a, Store, Option<(X, Store)>>;
+
+        fn transform<X: 'a>(&mut self, fx: KeyValF<'a, X>) -> Self::Target<X> {
+            match fx {
+                KeyValF::Get(k, cont) => StateT::new(move |s: Store| {
+                    let v = s.get(&k).cloned()?;
+                    Some((cont(v), s))
+                }),
+                KeyValF::Put(k, v, next) => StateT::new(move |mut s: Store| {
+                    s.insert(k, v);
+                    Some((next, s))
+                }),
+            }
+        }
+    }
+
+    /// Logs the instructions, every key holds its own name
+    struct Logger;
+
+    impl<'a> NaturalTransformation<'a, KeyValF<'a, ()>, Writer<(), Vec<String>>> for Logger {
+        type Target<X: 'a> = Writer<X, Vec<String>>;
+
+        fn transform<X: 'a>(&mut self, fx: KeyValF<'a, X>) -> Writer<X, Vec<String>> {
+            match fx {
+                KeyValF::Get(k, cont) => {
+                    Writer::new(cont(k.to_uppercase()), vec![format!("get {}", k)])
+                }
+                KeyValF::Put(k, v, next) => Writer::new(next, vec![format!("put {}={}", k, v)]),
+            }
+        }
+    }
+
+    /// Reads from a fixed configuration, which can't be written
+    struct ReadOnly<'s>(&'s Store);
+
+    impl<'a, 's> NaturalTransformation<'a, KeyValF<'a, ()>, Result<(), String>> for ReadOnly<'s> {
+        type Target<X: 'a> = Result<X, String>;
+
+        fn transform<X: 'a>(&mut self, fx: KeyValF<'a, X>) -> Result<X, String> {
+            match fx {
+                KeyValF::Get(k, cont) => match self.0.get(&k) {
+                    Some(v) => Ok(cont(v.clone())),
+                    None => Err(format!("missing key {}", k)),
+                },
+                KeyValF::Put(k, _, _) => Err(format!("cannot put {}", k)),
+            }
+        }
+    }
+
+    /// Moves every key into a namespace
+    struct Namespace(&'static str);
+
+    impl<'a> NaturalTransformation<'a, KeyValF<'a, ()>, KeyValF<'a, ()>> for Namespace {
+        type Target<X: 'a> = KeyValF<'a, X>;
+
+        fn transform<X: 'a>(&mut self, fx: KeyValF<'a, X>) -> KeyValF<'a, X> {
+            match fx {
+                KeyValF::Get(k, cont) => KeyValF::Get(format!("{}/{}", self.0, k), cont),
+                KeyValF::Put(k, v, next) => KeyValF::Put(format!("{}/{}", self.0, k), v, next),
+            }
+        }
+    }
+
+    #[test]
+    fn key_val_fold_map_state() {
+        let (result, store) = prog().fold_map(InMemory).execute(HashMap::new());
+
+        assert_eq!(result, "my love");
+        assert_eq!(store.len(), 2);
+        assert_eq!(store["1"], "ue");
+    }
+
+    #[test]
+    fn key_val_fold_map_state_t() {
+        let store = prog().fold_map(Strict).execute(HashMap::new());
+        assert_eq!(store.map(|(result, _)| result), Some("my love".to_string()));
+
+        assert_eq!(
+            get_key("missing").fold_map(Strict).execute(HashMap::new()),
+            None
+        );
+    }
+
+    #[test]
+    fn key_val_fold_map_writer_result() {
+        let (result, log) = prog().fold_map(Logger).execute();
+
+        assert_eq!(result, "2");
+        assert_eq!(log, vec!["put 1=ue", "put 2=my love", "get 2"]);
+
+        let config = HashMap::from([("2".to_string(), "two".to_string())]);
+        assert_eq!(
+            get_key("2").fold_map(ReadOnly(&config)),
+            Ok("two".to_string())
+        );
+        assert_eq!(
+            get_key("3").fold_map(ReadOnly(&config)),
+            Err("missing key 3".to_string())
+        );
+        assert_eq!(
+            prog().fold_map(ReadOnly(&config)),
+            Err("cannot put 1".to_string())
+        );
+    }
+
+    #[test]
+    fn key_val_hoist() {
+        let namespaced = prog().hoist(Namespace("app"));
+
+        assert_eq!(
+            "Put app/1,ue\nPut app/2,my love\nGet app/2\nreturn app/2",
+            eval_to_string(namespaced)
+        );
+    }
+
+    #[test]
+    fn key_val_fold_map_long_program() {
+        // 100k writes followed by a read, built from the last instruction backwards
+        let mut program: KeyVal<String> =
+            Free::Free(Box::new(KeyValF::Get("n".into(), Box::new(Pure))));
+        for i in (0..100_000).rev() {
+            program = Free::Free(Box::new(KeyValF::Put("n".into(), i.to_string(), program)));
+        }
+
+        let (result, _) = program.fold_map(InMemory).execute(HashMap::new());
+        assert_eq!(result, "99999");
+    }
+
+    #[test]
+    fn key_val_hoist_long_program() {
+        let mut program: KeyVal<String> = get_key("n");
+        for i in (0..100_000).rev() {
+            program = Free::Free(Box::new(KeyValF::Put("n".into(), i.to_string(), program)));
+        }
+
+        let (result, store) = program
+            .hoist(Namespace("app"))
+            .fold_map(InMemory)
+            .execute(HashMap::new());
+        assert_eq!(result, "99999");
+        assert_eq!(store.len(), 1);
+        assert_eq!(store["app/n"], "99999");
+    }
 }
@@ -0,0 +1,381 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::{
+    Applicative, Apply, Functor, FunctorOnce, FunctorOnceWrapped, Monoid, NaturalTransformation,
+};
+
+/// What an instruction of a `FreeAp` program hands back once interpreted:
+/// running it stores the result of the instruction, ready to be combined into the final value.
+pub type Effect<'a> = Box<dyn 'a + FnOnce()>;
+
+/// The free applicative functor: like `Free`, it turns an instruction functor into a DSL,
+/// but programs are only combined with `Apply` (no `bind`), so no instruction can depend on the result of another.
+///
+/// This makes a program a static sequence of instructions: it can be inspected before running it
+/// (e.g. list every key a configuration program reads) with `analyze`, and run with `fold_map`.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/free/docs/Control-Applicative-Free.html>
+///
+/// ```haskell
+/// data Ap f a where
+///   Pure :: a -> Ap f a
+///   Ap   :: f a -> Ap f (a -> b) -> Ap f b
+/// ```
+pub struct FreeAp<'a, F: FunctorOnce<'a> + 'a, A> {
+    /// The instructions of the program, in order
+    effects: Vec<F::Wrapped<Effect<'a>>>,
+    /// Builds the final value from the results stored by the effects
+    result: Box<dyn 'a + FnOnce() -> A>,
+}
+
+impl<'a, F: FunctorOnce<'a> + 'a, A: 'a> FreeAp<'a, F, A> {
+    /// A program without instructions.
+    pub fn pure(value: A) -> Self {
+        FreeAp {
+            effects: vec![],
+            result: Box::new(move || value),
+        }
+    }
+
+    /// Lift a single instruction into a program which runs it and returns its result.
+    pub fn lift_ap(command: F::Wrapped<A>) -> Self
+    where
+        F: FunctorOnceWrapped<'a>,
+    {
+        let slot = Rc::new(Cell::new(None));
+        let store = slot.clone();
+
+        FreeAp {
+            effects: vec![F::fmap_wrapped(command, move |a| {
+                Box::new(move || store.set(Some(a))) as Effect<'a>
+            })],
+            result: Box::new(move || slot.take().expect("every effect runs before the result")),
+        }
+    }
+
+    /// Summarize the program without running it: every instruction is turned into a `Monoid` by `nt`
+    /// (whose `Target<X>` is M for every X) and the results are combined in order.
+    pub fn analyze<M, N>(self, mut nt: N) -> M
+    where
+        M: Monoid,
+        N: NaturalTransformation<'a, F, M, Target<Effect<'a>> = M>,
+    {
+        self.effects
+            .into_iter()
+            .fold(M::mempty(), |acc, effect| acc.mappend(nt.transform(effect)))
+    }
+
+    /// Interpret the program into the applicative G, translating every instruction with `nt`.
+    ///
+    /// The translated instructions are chained in order with `lift_a2` into a single effect,
+    /// which runs them all before building the final value. The final value is only built from
+    /// that effect, so an applicative which stops at a failure (like `Option`, `Result` or `Validation`)
+    /// never builds it, and never reads the result of an instruction which didn't run.
+    pub fn fold_map<G, N>(self, mut nt: N) -> N::Target<A>
+    where
+        N: NaturalTransformation<'a, F, G>,
+        N::Target<()>: Applicative<'a, Unwrapped = ()>
+            + Functor<'a, Wrapped<()> = N::Target<()>>
+            + Functor<'a, Wrapped<Effect<'a>> = N::Target<Effect<'a>>>,
+        N::Target<Effect<'a>>: Apply<'a, Unwrapped = Effect<'a>>
+            + Functor<'a, Wrapped<Effect<'a>> = N::Target<Effect<'a>>>
+            + Functor<'a, Wrapped<()> = N::Target<()>>
+            + Functor<'a, Wrapped<A> = N::Target<A>>,
+    {
+        let unit = || <N::Target<()> as Applicative>::of(());
+
+        let effects = self.effects.into_iter().fold(
+            unit().fmap(|_| Box::new(|| ()) as Effect<'a>),
+            |acc, effect| {
+                acc.lift_a2(
+                    nt.transform(effect),
+                    |run: Effect<'a>, effect: Effect<'a>| {
+                        Box::new(move || {
+                            run();
+                            effect()
+                        }) as Effect<'a>
+                    },
+                )
+            },
+        );
+
+        let result = self.result;
+        effects.lift_a2::<_, (), A>(unit(), move |run, _| {
+            run();
+            result()
+        })
+    }
+}
+
+impl<'a, F: FunctorOnce<'a> + 'a, A: 'a> Functor<'a> for FreeAp<'a, F, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = FreeAp<'a, F, B>;
+
+    fn fmap<G, B: 'a>(self, f: G) -> Self::Wrapped<B>
+    where
+        G: Fn(Self::Unwrapped) -> B + 'a,
+    {
+        let result = self.result;
+        FreeAp {
+            effects: self.effects,
+            result: Box::new(move || f(result())),
+        }
+    }
+}
+
+impl<'a, F: FunctorOnce<'a> + 'a, A: 'a> Apply<'a> for FreeAp<'a, F, A> {
+    fn ap<G, B: 'a>(self, f: Self::Wrapped<G>) -> Self::Wrapped<B>
+    where
+        G: FnOnce(Self::Unwrapped) -> B + 'a,
+    {
+        self.lift_a2(f, |a, f| f(a))
+    }
+
+    fn lift_a2<G, B: 'a, C: 'a>(self, b: Self::Wrapped<B>, f: G) -> Self::Wrapped<C>
+    where
+        G: FnOnce(Self::Unwrapped, B) -> C + 'a,
+    {
+        let mut effects = self.effects;
+        effects.extend(b.effects);
+
+        let (r1, r2) = (self.result, b.result);
+        FreeAp {
+            effects,
+            result: Box::new(move || f(r1(), r2())),
+        }
+    }
+}
+
+impl<'a, F: FunctorOnce<'a> + 'a, A: 'a> Applicative<'a> for FreeAp<'a, F, A> {
+    fn of(value: Self::Unwrapped) -> Self::Wrapped<Self::Unwrapped> {
+        FreeAp::pure(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use crate::{
+        Applicative, Apply, FreeAp, Functor, FunctorOnce, FunctorOnceWrapped,
+        NaturalTransformation, Validation,
+    };
+
+    pub enum ConfigF<'a, A> {
+        Str(String, Box<dyn 'a + FnOnce(String) -> A>),
+        Int(String, Box<dyn 'a + FnOnce(i32) -> A>),
+    }
+
+    impl<'a, A: 'a> FunctorOnce<'a> for ConfigF<'a, A> {
+        type Unwrapped = A;
+
+        type Wrapped<B: 'a> = ConfigF<'a, B>;
+
+        fn fmap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+        where
+            F: FnOnce(Self::Unwrapped) -> B + 'a,
+        {
+            match self {
+                ConfigF::Str(k, cont) => ConfigF::Str(k, Box::new(move |s| f(cont(s)))),
+                ConfigF::Int(k, cont) => ConfigF::Int(k, Box::new(move |i| f(cont(i)))),
+            }
+        }
+    }
+
+    impl<'a, A: 'a> FunctorOnceWrapped<'a> for ConfigF<'a, A> {
+        fn fmap_wrapped<X: 'a, Y: 'a, F>(fx: ConfigF<'a, X>, f: F) -> ConfigF<'a, Y>
+        where
+            F: FnOnce(X) -> Y + 'a,
+        {
+            fx.fmap(f)
+        }
+    }
+
+    type Config<'a, A> = FreeAp<'a, ConfigF<'a, ()>, A>;
+
+    fn string<'a>(key: &str) -> Config<'a, String> {
+        FreeAp::lift_ap(ConfigF::Str(key.into(), Box::new(|s| s)))
+    }
+
+    fn int<'a>(key: &str) -> Config<'a, i32> {
+        FreeAp::lift_ap(ConfigF::Int(key.into(), Box::new(|i| i)))
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Server {
+        host: String,
+        port: i32,
+        workers: i32,
+    }
+
+    fn server<'a>() -> Config<'a, Server> {
+        string("host")
+            .lift_a2(int("port"), |host, port| (host, port))
+            .lift_a2(int("workers"), |(host, port), workers| Server {
+                host,
+                port,
+                workers,
+            })
+    }
+
+    /// The keys read by a program
+    struct Keys;
+
+    impl<'a> NaturalTransformation<'a, ConfigF<'a, ()>, Vec<String>> for Keys {
+        type Target<X: 'a> = Vec<String>;
+
+        fn transform<X: 'a>(&mut self, fx: ConfigF<'a, X>) -> Vec<String> {
+            match fx {
+                ConfigF::Str(k, _) => vec![k],
+                ConfigF::Int(k, _) => vec![format!("{} (int)", k)],
+            }
+        }
+    }
+
+    /// Reads from the environment, failing on the first missing or malformed key
+    struct Env(HashMap<&'static str, &'static str>);
+
+    impl<'a> NaturalTransformation<'a, ConfigF<'a, ()>, Result<(), String>> for Env {
+        type Target<X: 'a> = Result<X, String>;
+
+        fn transform<X: 'a>(&mut self, fx: ConfigF<'a, X>) -> Result<X, String> {
+            let read = |k: &str| self.0.get(k).ok_or(format!("missing {}", k));
+            match fx {
+                ConfigF::Str(k, cont) => read(&k).map(|v| cont(v.to_string())),
+                ConfigF::Int(k, cont) => read(&k)?
+                    .parse()
+                    .map(cont)
+                    .map_err(|_| format!("{} is not an int", k)),
+            }
+        }
+    }
+
+    /// Fills every key with a default value
+    struct Defaults;
+
+    impl<'a> NaturalTransformation<'a, ConfigF<'a, ()>, Option<()>> for Defaults {
+        type Target<X: 'a> = Option<X>;
+
+        fn transform<X: 'a>(&mut self, fx: ConfigF<'a, X>) -> Option<X> {
+            match fx {
+                ConfigF::Str(k, cont) => Some(cont(format!("<{}>", k))),
+                ConfigF::Int(_, cont) => Some(cont(0)),
+            }
+        }
+    }
+
+    /// Reads from the environment, reporting every missing key
+    struct Check(HashMap<&'static str, &'static str>);
+
+    impl<'a> NaturalTransformation<'a, ConfigF<'a, ()>, Validation<Vec<String>, ()>> for Check {
+        type Target<X: 'a> = Validation<Vec<String>, X>;
+
+        fn transform<X: 'a>(&mut self, fx: ConfigF<'a, X>) -> Validation<Vec<String>, X> {
+            match fx {
+                ConfigF::Str(k, cont) => match self.0.get(k.as_str()) {
+                    Some(v) => Validation::Success(cont(v.to_string())),
+                    None => Validation::Failure(vec![format!("missing {}", k)]),
+                },
+                ConfigF::Int(k, cont) => match self.0.get(k.as_str()).map(|v| v.parse()) {
+                    Some(Ok(i)) => Validation::Success(cont(i)),
+                    _ => Validation::Failure(vec![format!("missing {}", k)]),
+                },
+            }
+        }
+    }
+
+    /// Fills the keys found in the environment, failing on the first missing one
+    struct Lookup(HashMap<&'static str, &'static str>);
+
+    impl<'a> NaturalTransformation<'a, ConfigF<'a, ()>, Option<()>> for Lookup {
+        type Target<X: 'a> = Option<X>;
+
+        fn transform<X: 'a>(&mut self, fx: ConfigF<'a, X>) -> Option<X> {
+            match fx {
+                ConfigF::Str(k, cont) => self.0.get(k.as_str()).map(|v| cont(v.to_string())),
+                ConfigF::Int(k, cont) => self.0.get(k.as_str())?.parse().ok().map(cont),
+            }
+        }
+    }
+
+    #[test]
+    fn free_ap_analyze() {
+        assert_eq!(
+            server().analyze(Keys),
+            vec!["host", "port (int)", "workers (int)"]
+        );
+
+        assert_eq!(Config::pure(42).analyze(Keys), Vec::<String>::new());
+    }
+
+    #[test]
+    fn free_ap_fold_map_result() {
+        let env = HashMap::from([("host", "localhost"), ("port", "8080"), ("workers", "4")]);
+        assert_eq!(
+            server().fold_map(Env(env)),
+            Ok(Server {
+                host: "localhost".to_string(),
+                port: 8080,
+                workers: 4
+            })
+        );
+
+        let env = HashMap::from([("host", "localhost"), ("port", "http")]);
+        assert_eq!(
+            server().fold_map(Env(env)),
+            Err("port is not an int".to_string())
+        );
+    }
+
+    #[test]
+    fn free_ap_fold_map_option() {
+        assert_eq!(
+            server().fold_map(Defaults),
+            Some(Server {
+                host: "<host>".to_string(),
+                port: 0,
+                workers: 0
+            })
+        );
+    }
+
+    #[test]
+    fn free_ap_fold_map_short_circuit() {
+        // The first instruction fails: the following ones and the final value are skipped
+        let env = HashMap::from([("port", "8080"), ("workers", "4")]);
+        assert_eq!(server().fold_map(Lookup(env)), None);
+
+        let env = HashMap::from([("host", "localhost"), ("port", "8080")]);
+        assert_eq!(server().fold_map(Lookup(env)), None);
+
+        // Every instruction runs, but the final value isn't built
+        let env = HashMap::from([("port", "8080")]);
+        assert_eq!(
+            server().fold_map(Check(env)),
+            Validation::Failure(vec![
+                "missing host".to_string(),
+                "missing workers".to_string()
+            ])
+        );
+
+        let env = HashMap::from([("host", "localhost"), ("port", "8080"), ("workers", "4")]);
+        assert!(server().fold_map(Check(env)).is_success());
+    }
+
+    #[test]
+    fn free_ap_functor_applicative() {
+        let port = int("port").fmap(|p| p + 1);
+        let url = string("host").ap(<Config<_> as Applicative>::of(|h: String| {
+            format!("http://{}", h)
+        }));
+
+        let both = url.lift_a2(port, |u, p| format!("{}:{}", u, p));
+        assert_eq!(both.fold_map(Defaults), Some("http://<host>:1".to_string()));
+
+        assert_eq!(
+            Config::pure(41).fmap(|a| a + 1).fold_map(Defaults),
+            Some(42)
+        );
+    }
+}
@@ -16,6 +16,15 @@ pub trait Functor<'a> {
         F: Fn(Self::Unwrapped) -> B + 'a;
 }
 
+/// A `Functor` able to map any of its `Wrapped` types, like `FunctorOnceWrapped`.
+/// This is what lets recursive structures like `Cofree` map their children.
+pub trait FunctorWrapped<'a>: Functor<'a> {
+    /// fmap over any other value built by the same type constructor, turning an `f x` into an `f y`.
+    fn fmap_wrapped<X: 'a, Y: 'a, G>(fx: Self::Wrapped<X>, f: G) -> Self::Wrapped<Y>
+    where
+        G: Fn(X) -> Y + 'a;
+}
+
 /// A `Functor` trait where the `fmap` operation is a `FnOnce` instead of an `Fn`.
 pub trait FunctorOnce<'a> {
     type Unwrapped;
@@ -26,6 +35,18 @@ pub trait FunctorOnce<'a> {
         F: FnOnce(Self::Unwrapped) -> B + 'a;
 }
 
+/// A `FunctorOnce` able to map any of its `Wrapped` types.
+///
+/// Without higher-kinded types generic code can't tell that mapping a `Self::Wrapped<X>` gives back
+/// a `Self::Wrapped<Y>`, so an instance spells it out, usually by just calling `fx.fmap(f)`.
+/// This is what lets recursive structures like `Free` keep the same functor parameter.
+pub trait FunctorOnceWrapped<'a>: FunctorOnce<'a> {
+    /// fmap over any other value built by the same type constructor, turning an `f x` into an `f y`.
+    fn fmap_wrapped<X: 'a, Y: 'a, F>(fx: Self::Wrapped<X>, f: F) -> Self::Wrapped<Y>
+    where
+        F: FnOnce(X) -> Y + 'a;
+}
+
 impl<'a, A> Functor<'a> for Option<A> {
     type Unwrapped = A;
     type Wrapped<B: 'a> = Option<B>;
@@ -69,6 +90,33 @@ impl<'a, A> Functor<'a> for Vec<A> {
     }
 }
 
+impl<'a, A> FunctorWrapped<'a> for Option<A> {
+    fn fmap_wrapped<X: 'a, Y: 'a, G>(fx: Self::Wrapped<X>, f: G) -> Self::Wrapped<Y>
+    where
+        G: Fn(X) -> Y + 'a,
+    {
+        fx.fmap(f)
+    }
+}
+
+impl<'a, A, E> FunctorWrapped<'a> for Result<A, E> {
+    fn fmap_wrapped<X: 'a, Y: 'a, G>(fx: Self::Wrapped<X>, f: G) -> Self::Wrapped<Y>
+    where
+        G: Fn(X) -> Y + 'a,
+    {
+        fx.fmap(f)
+    }
+}
+
+impl<'a, A> FunctorWrapped<'a> for Vec<A> {
+    fn fmap_wrapped<X: 'a, Y: 'a, G>(fx: Self::Wrapped<X>, f: G) -> Self::Wrapped<Y>
+    where
+        G: Fn(X) -> Y + 'a,
+    {
+        fx.fmap(f)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::Functor;
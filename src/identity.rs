@@ -0,0 +1,254 @@
+use std::ops::ControlFlow;
+
+use crate::{
+    Applicative, Apply, Comonad, Functor, FunctorWrapped, Monad, MonadRec, MonadWrapped, ResultT,
+    State, StateT,
+};
+
+/// `Identity` is the trivial monad: it just wraps a value without adding any effect.
+///
+/// Its main use is as the base of a monad transformer stack: a transformer applied to `Identity`
+/// behaves like its plain counterpart, e.g. `StateT<S, Identity<(A, S)>>` is `State<S, A>`
+/// and `ResultT<Identity<Result<A, E>>>` is `Result<A, E>`.
+/// This way code written against transformers can be run in the pure case too.
+///
+/// The `From` conversions between `ResultT` over `Identity` and `Result`, and between `StateT` over `Identity`
+/// and `State` (which is implemented by it), just move the value.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/base/docs/Data-Functor-Identity.html>
+///
+/// ```haskell
+/// newtype Identity a = Identity { runIdentity :: a }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(transparent)]
+pub struct Identity<A> {
+    /// The wrapped value
+    runner: A,
+}
+
+impl<A> Identity<A> {
+    /// Wrap a value into an `Identity`.
+    pub fn new(value: A) -> Self {
+        Self { runner: value }
+    }
+
+    /// Extract the wrapped value.
+    pub fn execute(self) -> A {
+        self.runner
+    }
+}
+
+impl<'a, A> Functor<'a> for Identity<A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Identity<B>;
+
+    fn fmap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> B,
+    {
+        Identity::new(f(self.runner))
+    }
+}
+
+impl<'a, A> FunctorWrapped<'a> for Identity<A> {
+    fn fmap_wrapped<X: 'a, Y: 'a, G>(fx: Self::Wrapped<X>, f: G) -> Self::Wrapped<Y>
+    where
+        G: Fn(X) -> Y + 'a,
+    {
+        fx.fmap(f)
+    }
+}
+
+impl<'a, A> Apply<'a> for Identity<A> {
+    fn ap<F, B: 'a>(self, f: Self::Wrapped<F>) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> B + 'a,
+    {
+        Identity::new((f.runner)(self.runner))
+    }
+
+    fn lift_a2<F, B: 'a, C: 'a>(self, b: Self::Wrapped<B>, f: F) -> Self::Wrapped<C>
+    where
+        F: FnOnce(Self::Unwrapped, B) -> C,
+    {
+        Identity::new(f(self.runner, b.runner))
+    }
+}
+
+impl<'a, A: 'a> Applicative<'a> for Identity<A> {
+    fn of(value: Self::Unwrapped) -> Self::Wrapped<Self::Unwrapped> {
+        Identity::new(value)
+    }
+}
+
+impl<'a, A> Monad<'a> for Identity<A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Identity<B>;
+
+    fn bind<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> Self::Wrapped<B> + 'a,
+    {
+        f(self.runner)
+    }
+
+    fn of<T: 'a>(value: T) -> Self::Wrapped<T> {
+        Identity::new(value)
+    }
+}
+
+impl<'a, A> MonadWrapped<'a> for Identity<A> {
+    fn bind_wrapped<X: 'a, Y: 'a, F>(mx: Identity<X>, f: F) -> Identity<Y>
+    where
+        F: FnOnce(X) -> Identity<Y> + 'a,
+    {
+        mx.bind(f)
+    }
+}
+
+impl<'a, A> MonadRec<'a> for Identity<A> {
+    fn tail_rec_m<B: 'a, C: 'a, F>(init: B, mut f: F) -> Self::Wrapped<C>
+    where
+        F: FnMut(B) -> Self::Wrapped<ControlFlow<C, B>> + 'a,
+    {
+        let mut state = init;
+        loop {
+            match f(state).runner {
+                ControlFlow::Continue(next) => state = next,
+                ControlFlow::Break(c) => return Identity::new(c),
+            }
+        }
+    }
+}
+
+impl<'a, A> Comonad<'a> for Identity<A> {
+    fn extract(self) -> Self::Unwrapped {
+        self.runner
+    }
+
+    fn extend<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(Self) -> B + 'a,
+    {
+        Identity::new(f(self))
+    }
+}
+
+impl<'a, S: 'a, A: 'a> From<State<'a, S, A>> for StateT<'a, S, Identity<(A, S)>> {
+    fn from(state: State<'a, S, A>) -> Self {
+        state.runner
+    }
+}
+
+impl<'a, S: 'a, A: 'a> From<StateT<'a, S, Identity<(A, S)>>> for State<'a, S, A> {
+    fn from(state_t: StateT<'a, S, Identity<(A, S)>>) -> Self {
+        State { runner: state_t }
+    }
+}
+
+impl<A, E> From<Result<A, E>> for ResultT<Identity<Result<A, E>>> {
+    fn from(result: Result<A, E>) -> Self {
+        ResultT::new(Identity::new(result))
+    }
+}
+
+impl<A, E> From<ResultT<Identity<Result<A, E>>>> for Result<A, E> {
+    fn from(result_t: ResultT<Identity<Result<A, E>>>) -> Self {
+        result_t.execute().execute()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::m;
+    use crate::Apply;
+    use crate::Comonad;
+    use crate::Functor;
+    use crate::Identity;
+    use crate::Monad;
+    use crate::ResultT;
+    use crate::State;
+    use crate::StateT;
+
+    #[test]
+    fn identity_functor() {
+        let a = Identity::new(31337);
+        let b = a.fmap(|x| format!("{}", x));
+        assert_eq!(b.execute(), "31337".to_string());
+    }
+
+    #[test]
+    fn identity_lifta2() {
+        let a = Identity::new(40);
+        let res = a.lift_a2(Identity::new(2), |u1, u2| u1 + u2);
+        assert_eq!(res, Identity::new(42));
+    }
+
+    #[test]
+    fn identity_bind() {
+        let a = Identity::new(1);
+        let b = a.bind(|x| Identity::new(x + 41));
+        assert_eq!(b, Identity::new(42));
+    }
+
+    #[test]
+    fn identity_comonad() {
+        let a = Identity::new(20);
+        let b = a.extend(|w| w.extract() * 2 + 2);
+        assert_eq!(b.extract(), 42);
+    }
+
+    #[test]
+    fn identity_state_t_into_state() {
+        let s_t = StateT::new(|s: i32| Identity::new((s, s + 1)));
+        let s_t2 = s_t.bind(|a| StateT::new(move |s| Identity::new((a + s, s * 2))));
+
+        let state: State<i32, i32> = s_t2.into();
+
+        assert_eq!(state.execute(20), (41, 42));
+    }
+
+    #[test]
+    fn identity_state_into_state_t() {
+        let state = State::new(|s: i32| (s, s + 1));
+        let s_t: StateT<i32, Identity<(i32, i32)>> = state.into();
+
+        let s_t2 = m! {
+            a <- s_t;
+            StateT::new(move |s| Identity::new((a * 2, s)))
+        };
+
+        assert_eq!(s_t2.execute(21).execute(), (42, 22));
+    }
+
+    #[test]
+    fn identity_generic_transformer_code() {
+        fn incr<'a, M>() -> StateT<'a, i32, M>
+        where
+            M: 'a + Monad<'a, Unwrapped = ((), i32), Wrapped<((), i32)> = M>,
+        {
+            StateT::new(|s| M::of(((), s + 1)))
+        }
+
+        let pure: State<i32, ()> = incr::<Identity<_>>().into();
+        assert_eq!(pure.execute(41), ((), 42));
+
+        assert_eq!(incr::<Option<_>>().execute(41), Option::Some(((), 42)));
+    }
+
+    #[test]
+    fn identity_result_t_result() {
+        let r_t: ResultT<Identity<Result<i32, String>>> = Result::Ok(1).into();
+        let r_t2 = r_t.bind(|a| ResultT::new(Identity::new(Result::Ok(a + 41))));
+
+        assert_eq!(Result::from(r_t2), Result::Ok(42));
+
+        let r_t: ResultT<Identity<Result<i32, &str>>> = Result::Err("nope").into();
+        let r_t2 = r_t.bind(|a| ResultT::new(Identity::new(Result::Ok(a + 41))));
+
+        assert_eq!(Result::from(r_t2), Result::Err("nope"));
+    }
+}
@@ -15,6 +15,8 @@ pub use crate::monoid::Monoid;
 mod functor;
 pub use crate::functor::Functor;
 pub use crate::functor::FunctorOnce;
+pub use crate::functor::FunctorOnceWrapped;
+pub use crate::functor::FunctorWrapped;
 
 mod apply;
 pub use crate::apply::Apply;
@@ -24,6 +26,37 @@ pub use crate::applicative::Applicative;
 
 mod monad;
 pub use crate::monad::Monad;
+pub use crate::monad::MonadWrapped;
+
+mod monad_rec;
+pub use crate::monad_rec::MonadRec;
+
+mod alternative;
+pub use crate::alternative::Alternative;
+pub use crate::alternative::MonadPlus;
+
+mod monad_error;
+pub use crate::monad_error::MonadError;
+
+mod monad_state;
+pub use crate::monad_state::MonadState;
+
+mod monad_writer;
+pub use crate::monad_writer::MonadWriter;
+pub use crate::monad_writer::MonadWriterWrapped;
+
+mod bifunctor;
+pub use crate::bifunctor::Bifunctor;
+
+mod comonad;
+pub use crate::comonad::Comonad;
+
+mod contravariant;
+pub use crate::contravariant::Contravariant;
+
+mod divisible;
+pub use crate::divisible::Decidable;
+pub use crate::divisible::Divisible;
 
 mod foldable;
 pub use crate::foldable::Foldable;
@@ -31,15 +64,91 @@ pub use crate::foldable::Foldable;
 mod traversable;
 pub use crate::traversable::Traversable;
 
+mod eval;
+pub use crate::eval::Eval;
+
+mod identity;
+pub use crate::identity::Identity;
+
+mod validation;
+pub use crate::validation::Validation;
+
+mod non_empty;
+pub use crate::non_empty::NonEmpty;
+
 mod state;
 pub use crate::state::State;
 
 mod writer;
 pub use crate::writer::Writer;
 
+mod reader;
+pub use crate::reader::Reader;
+
+mod rws;
+pub use crate::rws::RWS;
+
+mod cont;
+pub use crate::cont::Cont;
+
+mod store;
+pub use crate::store::Store;
+
+mod env;
+pub use crate::env::Env;
+
+mod traced;
+pub use crate::traced::Traced;
+
+mod predicate;
+pub use crate::predicate::Predicate;
+
+mod comparator;
+pub use crate::comparator::Comparator;
+
+mod equivalence;
+pub use crate::equivalence::Equivalence;
+
+mod op;
+pub use crate::op::Op;
+
+mod optics;
+pub use crate::optics::Fold;
+pub use crate::optics::Iso;
+pub use crate::optics::Lens;
+pub use crate::optics::Prism;
+pub use crate::optics::Traversal;
+
 mod trans;
+pub use crate::trans::ContT;
+pub use crate::trans::Continuation;
+pub use crate::trans::Exit;
+pub use crate::trans::MonadTrans;
+pub use crate::trans::OptionT;
+pub use crate::trans::ReaderT;
 pub use crate::trans::ResultT;
 pub use crate::trans::StateT;
+pub use crate::trans::WriterT;
+pub use crate::trans::RWST;
+
+mod natural_transformation;
+pub use crate::natural_transformation::NaturalTransformation;
 
 mod free;
 pub use crate::free::Free;
+
+mod cofree;
+pub use crate::cofree::Cofree;
+
+mod codensity;
+pub use crate::codensity::Codensity;
+
+mod free_ap;
+pub use crate::free_ap::Effect;
+pub use crate::free_ap::FreeAp;
+
+mod program;
+pub use crate::program::Answer;
+pub use crate::program::Program;
+pub use crate::program::ProgramView;
+pub use crate::program::Reply;
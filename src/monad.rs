@@ -1,6 +1,6 @@
 /// `Monad` provides a way to link actions, and a way
 /// to bring plain values into the correct context (`Applicative`).
-
+///
 /// `Monad` should "derive" from `Applicative` and add only the `bind` operation, but due to the limits of the Rust type system
 /// (Higher-kinded Types are missing), we should define a dedicated trait to be able to (partially) support Monad Transformers and avoid
 /// some type madness
@@ -22,6 +22,18 @@ pub trait Monad<'a> {
     fn of<T: 'a>(value: T) -> Self::Wrapped<T>;
 }
 
+/// A `Monad` able to `bind` any of its `Wrapped` types.
+///
+/// In generic code `Self::Wrapped<B>` isn't known to be a `Monad` itself, so a transformer which
+/// has to bind the result of a continuation in its inner monad (e.g. `WriterT`, appending the log of
+/// the continuation to its own) asks for this trait instead of `Monad`.
+pub trait MonadWrapped<'a>: Monad<'a> {
+    /// Sequentially compose a wrapped value of this monad with `f`.
+    fn bind_wrapped<X: 'a, Y: 'a, F>(mx: Self::Wrapped<X>, f: F) -> Self::Wrapped<Y>
+    where
+        F: FnOnce(X) -> Self::Wrapped<Y> + 'a;
+}
+
 impl<'a, A> Monad<'a> for Option<A> {
     type Unwrapped = A;
     type Wrapped<B: 'a> = Option<B>;
@@ -64,12 +76,37 @@ impl<'a, A, E> Monad<'a> for Result<A, E> {
     }
 }
 
+impl<'a, A> MonadWrapped<'a> for Option<A> {
+    fn bind_wrapped<X: 'a, Y: 'a, F>(mx: Option<X>, f: F) -> Option<Y>
+    where
+        F: FnOnce(X) -> Option<Y> + 'a,
+    {
+        mx.bind(f)
+    }
+}
+
+impl<'a, A, E> MonadWrapped<'a> for Result<A, E> {
+    fn bind_wrapped<X: 'a, Y: 'a, F>(mx: Result<X, E>, f: F) -> Result<Y, E>
+    where
+        F: FnOnce(X) -> Result<Y, E> + 'a,
+    {
+        mx.bind(f)
+    }
+}
+
 // Took from: https://docs.rs/do-notation/latest/do_notation/
 
 /// Provides the Haskell monadic syntactic sugar `do`.
+///
+/// `guard!(cond);` stops the block with `Alternative::empty()` when `cond` doesn't hold.
 #[macro_export]
 macro_rules! m {
 
+// guard
+(guard ! ( $cond:expr ) ; $($r:tt)*) => {
+  if $cond { m!($($r)*) } else { $crate::Alternative::empty() }
+};
+
 // let-binding
 (let $p:pat = $e:expr ; $($r:tt)*) => {{
   let $p = $e;
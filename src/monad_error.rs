@@ -0,0 +1,85 @@
+use crate::Monad;
+
+/// `MonadError` is a `Monad` which can fail with an error of type E, and recover from it.
+///
+/// `throw_error` fails the computation, `catch_error` runs a handler computation on failure,
+/// `handle_error_with` recovers with a plain value, and `attempt` exposes the outcome as a `Result`
+/// so that the computation itself never fails.
+///
+/// Transformers pass the operations through their layer: a `StateT` over a `MonadError` can throw and catch
+/// the errors of the inner monad, restarting from the state it had before the failing computation.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/mtl/docs/Control-Monad-Except.html>
+///
+/// ```haskell
+/// class Monad m => MonadError e m | m -> e where
+///   throwError :: e -> m a
+///   catchError :: m a -> (e -> m a) -> m a
+/// ```
+pub trait MonadError<'a, E>: Monad<'a> + Sized {
+    /// Fail with the error `e`.
+    fn throw_error(e: E) -> Self;
+
+    /// Run the computation returned by `f` when this one fails.
+    fn catch_error<F>(self, f: F) -> Self
+    where
+        F: FnOnce(E) -> Self + 'a;
+
+    /// Recover from a failure with the value computed by `f` from the error.
+    fn handle_error_with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(E) -> Self::Unwrapped + 'a,
+        Self: Monad<'a, Wrapped<<Self as Monad<'a>>::Unwrapped> = Self> + 'a,
+        Self::Unwrapped: 'a,
+    {
+        self.catch_error(|e| Self::of(f(e)))
+    }
+
+    /// Turn a failure into an `Err` value, in a computation which doesn't fail.
+    fn attempt(self) -> Self::Wrapped<Result<Self::Unwrapped, E>>;
+}
+
+impl<'a, A: 'a, E: 'a> MonadError<'a, E> for Result<A, E> {
+    fn throw_error(e: E) -> Self {
+        Err(e)
+    }
+
+    fn catch_error<F>(self, f: F) -> Self
+    where
+        F: FnOnce(E) -> Self + 'a,
+    {
+        self.or_else(f)
+    }
+
+    fn attempt(self) -> Self::Wrapped<Result<Self::Unwrapped, E>> {
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::MonadError;
+
+    fn parse(s: &str) -> Result<i32, String> {
+        s.parse().map_err(|_| format!("{} is not a number", s))
+    }
+
+    #[test]
+    fn result_throw_catch() {
+        let r: Result<i32, String> = MonadError::throw_error("boom".to_string());
+        assert_eq!(r.clone().catch_error(|e| Ok(e.len() as i32)), Ok(4));
+        assert_eq!(r.handle_error_with(|_| 0), Ok(0));
+
+        assert_eq!(parse("42").catch_error(|_| Ok(0)), Ok(42));
+        assert_eq!(parse("x").catch_error(|_| parse("y")), parse("y"));
+    }
+
+    #[test]
+    fn result_attempt() {
+        assert_eq!(parse("42").attempt(), Ok(Ok(42)));
+        assert_eq!(
+            parse("x").attempt(),
+            Ok(Err("x is not a number".to_string()))
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use std::ops::ControlFlow;
+
+use crate::Monad;
+
+/// A `Monad` which supports stack-safe monadic loops.
+///
+/// `tail_rec_m` keeps applying `f` to the loop state for as long as it returns `Continue`,
+/// and stops with the `Break` value: each instance runs the loop iteratively, so it works for any
+/// number of steps. Interpreters (like `Free::fold_map`) use it to run long programs without recursion.
+///
+/// PureScript definition from <https://pursuit.purescript.org/packages/purescript-tailrec/docs/Control.Monad.Rec.Class>
+///
+/// ```haskell
+/// class Monad m <= MonadRec m where
+///   tailRecM :: forall a b. (a -> m (Step a b)) -> a -> m b
+/// ```
+pub trait MonadRec<'a>: Monad<'a> {
+    /// Run the monadic loop starting from `init`.
+    fn tail_rec_m<B: 'a, C: 'a, F>(init: B, f: F) -> Self::Wrapped<C>
+    where
+        F: FnMut(B) -> Self::Wrapped<ControlFlow<C, B>> + 'a;
+}
+
+impl<'a, A> MonadRec<'a> for Option<A> {
+    fn tail_rec_m<B: 'a, C: 'a, F>(init: B, mut f: F) -> Self::Wrapped<C>
+    where
+        F: FnMut(B) -> Self::Wrapped<ControlFlow<C, B>> + 'a,
+    {
+        let mut state = init;
+        loop {
+            match f(state)? {
+                ControlFlow::Continue(next) => state = next,
+                ControlFlow::Break(c) => return Some(c),
+            }
+        }
+    }
+}
+
+impl<'a, A, E> MonadRec<'a> for Result<A, E> {
+    fn tail_rec_m<B: 'a, C: 'a, F>(init: B, mut f: F) -> Self::Wrapped<C>
+    where
+        F: FnMut(B) -> Self::Wrapped<ControlFlow<C, B>> + 'a,
+    {
+        let mut state = init;
+        loop {
+            match f(state)? {
+                ControlFlow::Continue(next) => state = next,
+                ControlFlow::Break(c) => return Ok(c),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ops::ControlFlow;
+
+    use crate::Eval;
+    use crate::Identity;
+    use crate::MonadRec;
+    use crate::State;
+    use crate::StateT;
+    use crate::Writer;
+
+    const STEPS: u64 = 1_000_000;
+
+    fn count(n: u64) -> ControlFlow<u64, u64> {
+        if n < STEPS {
+            ControlFlow::Continue(n + 1)
+        } else {
+            ControlFlow::Break(n)
+        }
+    }
+
+    #[test]
+    fn option_result_tail_rec_m() {
+        assert_eq!(Option::<()>::tail_rec_m(0, |n| Some(count(n))), Some(STEPS));
+        assert_eq!(
+            Option::<()>::tail_rec_m(0, |n| if n < 10 { Some(count(n)) } else { None }),
+            None::<u64>
+        );
+
+        assert_eq!(
+            Result::<(), &str>::tail_rec_m(0, |n| Ok(count(n))),
+            Ok(STEPS)
+        );
+        assert_eq!(
+            Result::<(), &str>::tail_rec_m(0, |n| if n < 10 { Ok(count(n)) } else { Err("stop") }),
+            Err::<u64, _>("stop")
+        );
+    }
+
+    #[test]
+    fn identity_eval_tail_rec_m() {
+        assert_eq!(
+            Identity::<()>::tail_rec_m(0, |n| Identity::new(count(n))).execute(),
+            STEPS
+        );
+        assert_eq!(
+            Eval::<()>::tail_rec_m(0, |n| Eval::now(count(n))).run(),
+            STEPS
+        );
+    }
+
+    #[test]
+    fn state_writer_tail_rec_m() {
+        let state = State::<u64, ()>::tail_rec_m(0, |n| State::new(move |s| (count(n), s + 2)));
+        assert_eq!(state.execute(0), (STEPS, 2 * STEPS + 2));
+
+        let writer = Writer::<(), Vec<u64>>::tail_rec_m(0, |n| {
+            Writer::new(count(n), if n % 250_000 == 0 { vec![n] } else { vec![] })
+        });
+        assert_eq!(
+            writer.execute(),
+            (STEPS, vec![0, 250_000, 500_000, 750_000, 1_000_000])
+        );
+    }
+
+    #[test]
+    fn state_t_tail_rec_m() {
+        let s_t = StateT::<u64, Option<((), u64)>>::tail_rec_m(0, |n| {
+            StateT::new(move |s: u64| Some((count(n), s + 1)))
+        });
+        assert_eq!(s_t.execute(0), Some((STEPS, STEPS + 1)));
+
+        let s_t = StateT::<u64, Result<((), u64), String>>::tail_rec_m(0, |n| {
+            StateT::new(move |s: u64| {
+                if s < 3 {
+                    Ok((count(n), s + 1))
+                } else {
+                    Err(format!("stopped at {}", n))
+                }
+            })
+        });
+        assert_eq!(
+            s_t.execute(0),
+            Err::<(u64, u64), _>("stopped at 3".to_string())
+        );
+    }
+}
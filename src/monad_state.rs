@@ -0,0 +1,99 @@
+use crate::Monad;
+
+/// `MonadState` is a `Monad` carrying a state of type S, which can be read and replaced
+/// wherever the state sits in a transformer stack.
+///
+/// Every operation is built on `state`, which embeds a plain state function into the monad:
+/// `get` reads the state, `put` replaces it, `modify` updates it and `gets` reads a part of it.
+/// Like `of`, each operation returns `Self`, whose type is usually inferred from the rest of a do-block,
+/// so the same code runs in a `State`, a `StateT`, or any transformer over them.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/mtl/docs/Control-Monad-State-Class.html>
+///
+/// ```haskell
+/// class Monad m => MonadState s m | m -> s where
+///   get :: m s
+///   put :: s -> m ()
+///   state :: (s -> (a, s)) -> m a
+/// ```
+pub trait MonadState<'a, S>: Monad<'a> + Sized {
+    /// Embed a state function into the monad.
+    fn state<F>(f: F) -> Self
+    where
+        F: FnOnce(S) -> (Self::Unwrapped, S) + 'a;
+
+    /// Read the state.
+    fn get() -> Self
+    where
+        Self: Monad<'a, Unwrapped = S>,
+        S: Clone,
+    {
+        Self::state(|s| (s.clone(), s))
+    }
+
+    /// Replace the state.
+    fn put(s: S) -> Self
+    where
+        Self: Monad<'a, Unwrapped = ()>,
+        S: 'a,
+    {
+        Self::state(|_| ((), s))
+    }
+
+    /// Update the state with `f`.
+    fn modify<F>(f: F) -> Self
+    where
+        Self: Monad<'a, Unwrapped = ()>,
+        F: FnOnce(S) -> S + 'a,
+    {
+        Self::state(|s| ((), f(s)))
+    }
+
+    /// Read a part of the state, computed by `f`.
+    fn gets<F>(f: F) -> Self
+    where
+        F: FnOnce(&S) -> Self::Unwrapped + 'a,
+    {
+        Self::state(|s| (f(&s), s))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::m;
+    use crate::Monad;
+    use crate::MonadState;
+    use crate::ResultT;
+    use crate::State;
+    use crate::StateT;
+
+    /// Push on a stack, wherever the stack is
+    fn push<'a, M: MonadState<'a, Vec<i32>, Unwrapped = ()>>(n: i32) -> M {
+        M::modify(move |mut s: Vec<i32>| {
+            s.push(n);
+            s
+        })
+    }
+
+    #[test]
+    fn generic_state_code() {
+        // As usual in `m!`, the type of a step whose value is discarded can't be inferred
+        let in_state: State<Vec<i32>, ()> = m! {
+            push::<State<_, _>>(1);
+            push(2)
+        };
+        assert_eq!(in_state.execute(vec![]), ((), vec![1, 2]));
+
+        type Stack<'a, A> = ResultT<StateT<'a, Vec<i32>, Option<(Result<A, String>, Vec<i32>)>>>;
+
+        let in_stack: Stack<usize> = m! {
+            push::<Stack<_>>(1);
+            push::<Stack<_>>(2);
+            Stack::gets(|s: &Vec<i32>| s.len())
+        };
+        assert_eq!(
+            in_stack.execute().execute(vec![0]),
+            Some((Ok(3), vec![0, 1, 2]))
+        );
+    }
+}
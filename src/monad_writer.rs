@@ -0,0 +1,76 @@
+use crate::{Monad, Monoid};
+
+/// `MonadWriter` is a `Monad` producing a log of type W alongside its values,
+/// wherever the log sits in a transformer stack.
+///
+/// `writer` embeds a value and a log, `tell` only writes to the log, `listen` (and `listens`) expose
+/// the log written by a computation to the rest of the program, while `censor` (and `pass`) rewrite it.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/mtl/docs/Control-Monad-Writer-Class.html>
+///
+/// ```haskell
+/// class (Monoid w, Monad m) => MonadWriter w m | m -> w where
+///   writer :: (a, w) -> m a
+///   tell :: w -> m ()
+///   listen :: m a -> m (a, w)
+///   pass :: m (a, w -> w) -> m a
+/// ```
+pub trait MonadWriter<'a, W: Monoid + 'a>: Monad<'a> + Sized {
+    /// Embed a value and a log into the monad.
+    fn writer(a: Self::Unwrapped, w: W) -> Self;
+
+    /// Append `w` to the log.
+    fn tell(w: W) -> Self
+    where
+        Self: Monad<'a, Unwrapped = ()>,
+    {
+        Self::writer((), w)
+    }
+
+    /// Run the computation, and add the log it writes to its value.
+    fn listen(self) -> Self::Wrapped<(Self::Unwrapped, W)>
+    where
+        W: Clone;
+
+    /// Run the computation, and add a summary of the log it writes, computed by `f`, to its value.
+    #[allow(clippy::type_complexity)]
+    fn listens<B: 'a, F>(self, f: F) -> Self::Wrapped<(Self::Unwrapped, B)>
+    where
+        W: Clone,
+        F: FnOnce(&W) -> B + 'a,
+        Self::Unwrapped: 'a,
+        Self::Wrapped<(Self::Unwrapped, W)>: Monad<
+            'a,
+            Unwrapped = (Self::Unwrapped, W),
+            Wrapped<(Self::Unwrapped, B)> = Self::Wrapped<(Self::Unwrapped, B)>,
+        >,
+    {
+        self.listen()
+            .bind::<_, (Self::Unwrapped, B)>(move |(a, w)| Self::of((a, f(&w))))
+    }
+
+    /// Run the computation, and rewrite the log it writes with `f`.
+    fn censor<F>(self, f: F) -> Self
+    where
+        F: FnOnce(W) -> W + 'a;
+
+    /// Run a computation yielding a value and a function, and rewrite the log it writes with that function.
+    fn pass<B: 'a, F>(self) -> Self::Wrapped<B>
+    where
+        Self: MonadWriterWrapped<'a, W> + Monad<'a, Unwrapped = (B, F), Wrapped<(B, F)> = Self>,
+        F: FnOnce(W) -> W + 'a,
+    {
+        Self::pass_wrapped(self)
+    }
+}
+
+/// A `MonadWriter` able to `pass` any of its `Wrapped` types, like `MonadWrapped`.
+///
+/// A transformer passes the computation of its inner monad, whose type is only known
+/// as one of its `Wrapped` types, so it asks its inner monad for this trait.
+pub trait MonadWriterWrapped<'a, W: Monoid + 'a>: MonadWriter<'a, W> {
+    /// `pass` for a wrapped value of this monad.
+    fn pass_wrapped<B: 'a, F>(m: Self::Wrapped<(B, F)>) -> Self::Wrapped<B>
+    where
+        F: FnOnce(W) -> W + 'a;
+}
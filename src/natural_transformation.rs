@@ -0,0 +1,22 @@
+use crate::FunctorOnce;
+
+/// A natural transformation `F ~> G` turns an `F<X>` into a `G<X>` for every X, without looking at the X:
+/// it only changes the "container" (e.g. a DSL instruction into the monad which runs it).
+///
+/// Since Rust has no higher-kinded types, F is given by any of its instances
+/// (e.g. `KeyValF<'a, ()>` stands for `KeyValF`) and `Target<X>` spells out G applied to X,
+/// so G can be a `Monad` (to interpret a `Free` program with `fold_map`) as well as
+/// another instruction `FunctorOnce` (to `hoist` a `Free` program).
+///
+/// Haskell definition from <https://hackage.haskell.org/package/natural-transformation/docs/Control-Natural.html>
+///
+/// ```haskell
+/// type f ~> g = forall x. f x -> g x
+/// ```
+pub trait NaturalTransformation<'a, F: FunctorOnce<'a>, G> {
+    /// G applied to X
+    type Target<X: 'a>;
+
+    /// Transform a single `F<X>` into a `G<X>`.
+    fn transform<X: 'a>(&mut self, fx: F::Wrapped<X>) -> Self::Target<X>;
+}
@@ -0,0 +1,323 @@
+use crate::{Applicative, Comonad, Foldable, Functor, Monoid, Semigroup, Traversable};
+
+/// A list which always contains at least one element: its `head`, followed by the `tail`.
+///
+/// As a `Comonad` the head is the focus: `extend` applies a function to every non-empty suffix of the list.
+///
+/// Since there is always a first element, the elements can be reduced with a `Semigroup` alone (`sconcat`, `fold_map1`),
+/// without the `mempty` that a fold of a possibly empty collection needs.
+///
+/// Like `Vec`, `NonEmpty` is neither an `Apply` nor a `Monad` in this crate: its `bind` calls its function
+/// once per element, while those traits take `FnOnce` functions.
+/// `bind`, `ap` and `lift_a2` (with `singleton` as `pure`) are inherent methods taking `Fn`, which `m!` works with.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/base/docs/Data-List-NonEmpty.html>
+///
+/// ```haskell
+/// data NonEmpty a = a :| [a]
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NonEmpty<A> {
+    head: A,
+    tail: Vec<A>,
+}
+
+impl<A> NonEmpty<A> {
+    /// Build a list from its first element and the rest.
+    pub fn new(head: A, tail: Vec<A>) -> Self {
+        NonEmpty { head, tail }
+    }
+
+    /// A list of one element.
+    pub fn singleton(head: A) -> Self {
+        NonEmpty::new(head, vec![])
+    }
+
+    /// Build a list from a `Vec`, unless it is empty.
+    pub fn from_vec(mut values: Vec<A>) -> Option<Self> {
+        if values.is_empty() {
+            None
+        } else {
+            let head = values.remove(0);
+            Some(NonEmpty::new(head, values))
+        }
+    }
+
+    /// The first element.
+    pub fn head(&self) -> &A {
+        &self.head
+    }
+
+    /// The elements after the first one.
+    pub fn tail(&self) -> &[A] {
+        &self.tail
+    }
+
+    /// The number of elements, never 0.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.tail.len() + 1
+    }
+
+    /// Iterate over the elements in order.
+    pub fn iter(&self) -> impl Iterator<Item = &A> {
+        std::iter::once(&self.head).chain(self.tail.iter())
+    }
+
+    /// Map every element to a `Semigroup` and combine the results from left to right.
+    ///
+    /// ```haskell
+    /// foldMap1 :: Semigroup m => (a -> m) -> NonEmpty a -> m
+    /// ```
+    pub fn fold_map1<S: Semigroup, F>(self, f: F) -> S
+    where
+        F: Fn(A) -> S,
+    {
+        let head = f(self.head);
+        self.tail.into_iter().map(f).fold(head, S::mappend)
+    }
+
+    /// Apply `f` to every element and concatenate the resulting lists.
+    pub fn bind<B, F>(self, f: F) -> NonEmpty<B>
+    where
+        F: Fn(A) -> NonEmpty<B>,
+    {
+        self.fold_map1(f)
+    }
+
+    /// Apply every function of `fs` to every element, e.g. `[f, g] <*> [x, y] == [f x, f y, g x, g y]`.
+    pub fn ap<B, F>(self, fs: NonEmpty<F>) -> NonEmpty<B>
+    where
+        A: Clone,
+        F: Fn(A) -> B,
+    {
+        fs.bind(|f| {
+            NonEmpty::new(
+                f(self.head.clone()),
+                self.tail.iter().cloned().map(f).collect(),
+            )
+        })
+    }
+
+    /// Apply `f` to every pair of elements of the two lists.
+    pub fn lift_a2<B: Clone, C, F>(self, b: NonEmpty<B>, f: F) -> NonEmpty<C>
+    where
+        A: Clone,
+        F: Fn(A, B) -> C,
+    {
+        self.bind(|x| {
+            NonEmpty::new(
+                f(x.clone(), b.head.clone()),
+                b.tail.iter().map(|y| f(x.clone(), y.clone())).collect(),
+            )
+        })
+    }
+}
+
+impl<A: Semigroup> NonEmpty<A> {
+    /// Combine all the elements with `mappend`, from left to right.
+    ///
+    /// ```haskell
+    /// sconcat :: Semigroup a => NonEmpty a -> a
+    /// ```
+    pub fn sconcat(self) -> A {
+        self.tail.into_iter().fold(self.head, A::mappend)
+    }
+}
+
+impl<A> IntoIterator for NonEmpty<A> {
+    type Item = A;
+    type IntoIter = std::iter::Chain<std::iter::Once<A>, std::vec::IntoIter<A>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::once(self.head).chain(self.tail)
+    }
+}
+
+impl<A> From<NonEmpty<A>> for Vec<A> {
+    fn from(non_empty: NonEmpty<A>) -> Self {
+        non_empty.into_iter().collect()
+    }
+}
+
+/// Concatenation.
+impl<A> Semigroup for NonEmpty<A> {
+    fn mappend(mut self, other: Self) -> Self {
+        self.tail.extend(other);
+        self
+    }
+}
+
+impl<'a, A> Functor<'a> for NonEmpty<A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = NonEmpty<B>;
+
+    fn fmap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(Self::Unwrapped) -> B + 'a,
+    {
+        NonEmpty::new(f(self.head), self.tail.into_iter().map(f).collect())
+    }
+}
+
+impl<A: Monoid> Foldable for NonEmpty<A> {
+    type Unwrapped = A;
+
+    fn foldr<B: Monoid, F>(self, init: B, folder: F) -> B
+    where
+        F: Fn(B, &Self::Unwrapped) -> B,
+    {
+        std::iter::once(&self.head)
+            .chain(self.tail.iter())
+            .rfold(init, folder)
+    }
+}
+
+impl<'a, A> Traversable<'a> for NonEmpty<A> {
+    fn traverse<F, B: 'a, W>(self, f: F) -> W::Wrapped<Self::Wrapped<B>>
+    where
+        F: Fn(&Self::Unwrapped) -> W::Wrapped<B>,
+        W: Applicative<'a, Unwrapped = Self::Wrapped<B>, Wrapped<Self::Wrapped<B>> = W> + 'a,
+        <W as Functor<'a>>::Wrapped<B>: Functor<'a, Unwrapped = B, Wrapped<Self::Wrapped<B>> = W>,
+        <W as Functor<'a>>::Wrapped<Self::Wrapped<B>>: Applicative<'a>,
+        <Self as Functor<'a>>::Wrapped<B>: 'a,
+    {
+        // The head gives the first effect, no empty list is needed to start from
+        let head = f(&self.head).fmap(NonEmpty::singleton);
+
+        self.tail.iter().fold(head, |acc, a| {
+            acc.lift_a2(f(a), |mut acc: NonEmpty<B>, b| {
+                acc.tail.push(b);
+                acc
+            })
+        })
+    }
+
+    fn sequence_a<B: 'a, W>(self) -> W::Wrapped<Self::Wrapped<B>>
+    where
+        W: Applicative<'a, Unwrapped = Self::Wrapped<B>, Wrapped<Self::Wrapped<B>> = W> + 'a,
+        <W as Functor<'a>>::Wrapped<B>: Functor<'a, Unwrapped = B, Wrapped<Self::Wrapped<B>> = W>,
+        W: Applicative<'a, Wrapped<B> = Self::Unwrapped>,
+        <Self as Functor<'a>>::Wrapped<B>: 'a,
+        Self::Unwrapped: Applicative<'a> + 'a + Copy,
+    {
+        self.traverse::<_, _, W>(|a| *a)
+    }
+}
+
+impl<'a, A: Clone> Comonad<'a> for NonEmpty<A> {
+    fn extract(self) -> Self::Unwrapped {
+        self.head
+    }
+
+    fn extend<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(Self) -> B + 'a,
+    {
+        // extend f w@(_ :| xs) = f w :| go xs
+        let suffixes = (0..self.tail.len())
+            .map(|i| {
+                f(NonEmpty::new(
+                    self.tail[i].clone(),
+                    self.tail[i + 1..].to_vec(),
+                ))
+            })
+            .collect();
+        NonEmpty::new(f(self), suffixes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::m;
+    use crate::Comonad;
+    use crate::Foldable;
+    use crate::Functor;
+    use crate::NonEmpty;
+    use crate::Semigroup;
+    use crate::Traversable;
+    use crate::Validation;
+
+    #[test]
+    fn non_empty_construct() {
+        let xs = NonEmpty::new(1, vec![2, 3]);
+        assert_eq!(*xs.head(), 1);
+        assert_eq!(xs.tail(), &[2, 3]);
+        assert_eq!(xs.len(), 3);
+        assert_eq!(xs.iter().sum::<i32>(), 6);
+
+        assert_eq!(NonEmpty::from_vec(vec![1, 2, 3]), Some(xs));
+        assert_eq!(NonEmpty::<i32>::from_vec(vec![]), None);
+        assert_eq!(Vec::from(NonEmpty::singleton(42)), vec![42]);
+    }
+
+    #[test]
+    fn non_empty_functor_comonad() {
+        let xs = NonEmpty::new(1, vec![2, 3, 4]).fmap(|x| x * 10);
+        assert_eq!(xs, NonEmpty::new(10, vec![20, 30, 40]));
+
+        // Moving averages of the remaining values
+        let averages = xs.extend(|w| w.iter().sum::<i32>() / w.len() as i32);
+        assert_eq!(averages, NonEmpty::new(25, vec![30, 35, 40]));
+        assert_eq!(averages.extract(), 25);
+    }
+
+    #[test]
+    fn non_empty_semigroup_sconcat() {
+        let xs = NonEmpty::new(1, vec![2]).mappend(NonEmpty::new(3, vec![4]));
+        assert_eq!(xs, NonEmpty::new(1, vec![2, 3, 4]));
+        assert_eq!(xs.clone().sconcat(), 10);
+        assert_eq!(xs.clone().foldr(0, |acc, x| acc * 10 + x), 4321);
+
+        // No mempty needed: the maximum of a list that can't be empty
+        #[derive(Debug, PartialEq)]
+        struct Max(i32);
+        impl Semigroup for Max {
+            fn mappend(self, other: Self) -> Self {
+                Max(self.0.max(other.0))
+            }
+        }
+        assert_eq!(xs.fold_map1(Max), Max(4));
+    }
+
+    #[test]
+    fn non_empty_list_monad() {
+        let pairs = m! {
+            x <- NonEmpty::new(1, vec![2]);
+            y <- NonEmpty::new('a', vec!['b']);
+            NonEmpty::singleton((x, y))
+        };
+        assert_eq!(
+            pairs,
+            NonEmpty::new((1, 'a'), vec![(1, 'b'), (2, 'a'), (2, 'b')])
+        );
+
+        let fs: NonEmpty<fn(i32) -> i32> = NonEmpty::new(|x| x + 1, vec![|x| x * 10]);
+        assert_eq!(
+            NonEmpty::new(1, vec![2]).ap(fs),
+            NonEmpty::new(2, vec![3, 10, 20])
+        );
+        assert_eq!(
+            NonEmpty::new(1, vec![2]).lift_a2(NonEmpty::singleton(40), |a, b| a + b),
+            NonEmpty::new(41, vec![42])
+        );
+    }
+
+    #[test]
+    fn non_empty_traverse() {
+        let parsed = NonEmpty::new("1", vec!["2", "3"])
+            .traverse::<_, _, Option<_>>(|s| s.parse::<i32>().ok());
+        assert_eq!(parsed, Some(NonEmpty::new(1, vec![2, 3])));
+
+        let checked =
+            NonEmpty::new(1, vec![-2, 3, -4]).traverse::<_, _, Validation<Vec<i32>, _>>(|&n| {
+                if n > 0 {
+                    Validation::Success(n)
+                } else {
+                    Validation::Failure(vec![n])
+                }
+            });
+        assert_eq!(checked, Validation::Failure(vec![-2, -4]));
+    }
+}
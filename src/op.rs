@@ -0,0 +1,91 @@
+use std::{convert::Infallible, rc::Rc};
+
+use crate::{Contravariant, Decidable, Divisible, Monoid};
+
+/// A function consuming values of type A into an R, seen as a contravariant functor on A.
+///
+/// With a `Monoid` R it is an encoder: `Divisible` concatenates the encodings of the parts of a value,
+/// `Decidable` picks the encoding of each alternative, so structured encoders (to `String`, `Vec<u8>`, ...)
+/// are built from smaller ones.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/base/docs/Data-Functor-Contravariant.html>
+///
+/// ```haskell
+/// newtype Op a b = Op { getOp :: b -> a }
+/// ```
+pub struct Op<'a, R, A> {
+    runner: Rc<dyn 'a + Fn(&A) -> R>,
+}
+
+impl<'a, R: 'a, A: 'a> Op<'a, R, A> {
+    /// Constructs a new `Op` from the function it runs.
+    pub fn new<F>(runner: F) -> Self
+    where
+        F: Fn(&A) -> R + 'a,
+    {
+        Op {
+            runner: Rc::new(runner),
+        }
+    }
+
+    /// Run the function on `a`.
+    pub fn run(&self, a: &A) -> R {
+        (self.runner)(a)
+    }
+}
+
+impl<'a, R, A> Clone for Op<'a, R, A> {
+    fn clone(&self) -> Self {
+        Op {
+            runner: self.runner.clone(),
+        }
+    }
+}
+
+impl<'a, R: 'a, A: 'a> Contravariant<'a> for Op<'a, R, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Op<'a, R, B>;
+
+    fn contramap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(&B) -> Self::Unwrapped + 'a,
+    {
+        Op::new(move |b| self.run(&f(b)))
+    }
+}
+
+impl<'a, R: Monoid + 'a, A: 'a> Divisible<'a> for Op<'a, R, A> {
+    fn divide<F, B: 'a, C: 'a>(f: F, fb: Op<'a, R, B>, fc: Op<'a, R, C>) -> Self
+    where
+        F: Fn(&A) -> (B, C) + 'a,
+    {
+        Op::new(move |a| {
+            let (b, c) = f(a);
+            fb.run(&b).mappend(fc.run(&c))
+        })
+    }
+
+    fn conquer() -> Self {
+        Op::new(|_| R::mempty())
+    }
+}
+
+impl<'a, R: Monoid + 'a, A: 'a> Decidable<'a> for Op<'a, R, A> {
+    fn choose<F, B: 'a, C: 'a>(f: F, fb: Op<'a, R, B>, fc: Op<'a, R, C>) -> Self
+    where
+        F: Fn(&A) -> Result<B, C> + 'a,
+    {
+        Op::new(move |a| match f(a) {
+            Ok(b) => fb.run(&b),
+            Err(c) => fc.run(&c),
+        })
+    }
+
+    fn lose<F>(f: F) -> Self
+    where
+        F: Fn(&A) -> Infallible + 'a,
+    {
+        Op::new(move |a| match f(a) {})
+    }
+}
@@ -0,0 +1,111 @@
+use std::rc::Rc;
+
+use crate::{Category, Monoid, Semigroupoid};
+
+/// A `Fold` reads zero or more parts A of a structure S, without being able to update them.
+/// Every `Lens` and `Traversal` can be seen as a `Fold` (`as_fold`), and `fold_map` summarizes
+/// the parts with a `Monoid`.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/lens/docs/Control-Lens-Fold.html>
+///
+/// ```haskell
+/// type Fold s a = forall f. (Contravariant f, Applicative f) => (a -> f a) -> s -> f s
+/// folding :: Foldable f => (s -> f a) -> Fold s a
+/// ```
+#[allow(clippy::type_complexity)]
+pub struct Fold<'a, S, A> {
+    /// Collect the parts, from left to right
+    to_vec: Rc<dyn 'a + Fn(&S) -> Vec<A>>,
+}
+
+impl<'a, S: 'a, A: 'a> Fold<'a, S, A> {
+    /// Constructs a new `Fold` from the function collecting the parts.
+    pub fn new<T>(to_vec: T) -> Self
+    where
+        T: Fn(&S) -> Vec<A> + 'a,
+    {
+        Fold {
+            to_vec: Rc::new(to_vec),
+        }
+    }
+
+    /// All the parts in focus, from left to right.
+    pub fn to_vec(&self, s: &S) -> Vec<A> {
+        (self.to_vec)(s)
+    }
+
+    /// The first part in focus, if any.
+    pub fn preview(&self, s: &S) -> Option<A> {
+        self.to_vec(s).into_iter().next()
+    }
+
+    /// Map every part in focus to a `Monoid` and combine the results, from left to right.
+    pub fn fold_map<M: Monoid, F>(&self, s: &S, f: F) -> M
+    where
+        F: Fn(A) -> M,
+    {
+        self.to_vec(s)
+            .into_iter()
+            .fold(M::mempty(), |acc, a| acc.mappend(f(a)))
+    }
+
+    /// Read the parts B of each part in focus.
+    pub fn then<B: 'a>(self, inner: Fold<'a, A, B>) -> Fold<'a, S, B> {
+        Fold::new(move |s| {
+            self.to_vec(s)
+                .iter()
+                .flat_map(|a| inner.to_vec(a))
+                .collect()
+        })
+    }
+}
+
+impl<'a, S: Clone + 'a> Fold<'a, S, S> {
+    /// The fold reading the whole structure.
+    pub fn id() -> Self {
+        Fold::new(|s: &S| vec![s.clone()])
+    }
+}
+
+impl<'a, S, A> Clone for Fold<'a, S, A> {
+    fn clone(&self) -> Self {
+        Fold {
+            to_vec: self.to_vec.clone(),
+        }
+    }
+}
+
+/// `f.compose(g)` reads with `g` first, then with `f`.
+impl<'a, S: 'a> Semigroupoid for Fold<'a, S, S> {
+    fn compose(self, other: Self) -> Self {
+        other.then(self)
+    }
+}
+
+impl<'a, S: Clone + 'a> Category for Fold<'a, S, S> {
+    fn identity(self) -> Self {
+        Fold::id()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Fold;
+    use crate::Lens;
+
+    #[test]
+    fn fold_fold_map() {
+        let words = Fold::new(|s: &String| s.split_whitespace().map(String::from).collect());
+        let line = "fold the words".to_string();
+
+        assert_eq!(words.preview(&line), Some("fold".to_string()));
+        assert_eq!(words.fold_map(&line, |w| w.len() as i32), 12);
+
+        let first = Lens::new(|p: &(String, i32)| p.0.clone(), |p, s| (s, p.1));
+        let first_words = first.as_fold().then(words);
+        assert_eq!(
+            first_words.fold_map(&("a b".to_string(), 1), |w| w.to_uppercase()),
+            "AB"
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::rc::Rc;
+
+use crate::{Category, Lens, Prism, Semigroupoid};
+
+/// An `Iso` is a lossless conversion between S and A, in both directions:
+/// `from(to(s)) == s` and `to(from(a)) == a`.
+///
+/// It is the strongest optic: an `Iso` is both a `Lens` and a `Prism`, and it can be reversed.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/lens/docs/Control-Lens-Iso.html>
+///
+/// ```haskell
+/// type Iso s t a b = forall p f. (Profunctor p, Functor f) => p a (f b) -> p s (f t)
+/// iso :: (s -> a) -> (b -> t) -> Iso s t a b
+/// ```
+pub struct Iso<'a, S, A> {
+    /// Convert from S to A
+    to: Rc<dyn 'a + Fn(S) -> A>,
+    /// Convert back from A to S
+    from: Rc<dyn 'a + Fn(A) -> S>,
+}
+
+impl<'a, S: 'a, A: 'a> Iso<'a, S, A> {
+    /// Constructs a new `Iso` from the two conversions.
+    pub fn new<T, F>(to: T, from: F) -> Self
+    where
+        T: Fn(S) -> A + 'a,
+        F: Fn(A) -> S + 'a,
+    {
+        Iso {
+            to: Rc::new(to),
+            from: Rc::new(from),
+        }
+    }
+
+    /// Convert from S to A.
+    pub fn to(&self, s: S) -> A {
+        (self.to)(s)
+    }
+
+    /// Convert back from A to S.
+    pub fn from(&self, a: A) -> S {
+        (self.from)(a)
+    }
+
+    /// Update an S by working on its A representation.
+    pub fn over<F>(&self, s: S, f: F) -> S
+    where
+        F: FnOnce(A) -> A,
+    {
+        self.from(f(self.to(s)))
+    }
+
+    /// The same conversion, in the opposite direction.
+    pub fn reverse(self) -> Iso<'a, A, S> {
+        Iso {
+            to: self.from,
+            from: self.to,
+        }
+    }
+
+    /// Convert further from A to B.
+    pub fn then<B: 'a>(self, inner: Iso<'a, A, B>) -> Iso<'a, S, B> {
+        let outer = self.clone();
+        let inner_from = inner.clone();
+        Iso::new(
+            move |s| inner.to(outer.to(s)),
+            move |b| self.from(inner_from.from(b)),
+        )
+    }
+}
+
+impl<'a, S: Clone + 'a, A: 'a> Iso<'a, S, A> {
+    /// See the iso as a lens on the A representation of S.
+    pub fn as_lens(self) -> Lens<'a, S, A> {
+        let iso = self.clone();
+        Lens::new(move |s: &S| iso.to(s.clone()), move |_, a| self.from(a))
+    }
+
+    /// See the iso as a prism which always matches.
+    pub fn as_prism(self) -> Prism<'a, S, A> {
+        let iso = self.clone();
+        Prism::new(move |s: &S| Some(iso.to(s.clone())), move |a| self.from(a))
+    }
+}
+
+impl<'a, S: 'a> Iso<'a, S, S> {
+    /// The identity conversion.
+    pub fn id() -> Self {
+        Iso::new(|s| s, |s| s)
+    }
+}
+
+impl<'a, S, A> Clone for Iso<'a, S, A> {
+    fn clone(&self) -> Self {
+        Iso {
+            to: self.to.clone(),
+            from: self.from.clone(),
+        }
+    }
+}
+
+/// `f.compose(g)` converts with `g` first, then with `f`.
+impl<'a, S: 'a> Semigroupoid for Iso<'a, S, S> {
+    fn compose(self, other: Self) -> Self {
+        other.then(self)
+    }
+}
+
+impl<'a, S: 'a> Category for Iso<'a, S, S> {
+    fn identity(self) -> Self {
+        Iso::id()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Iso;
+    use crate::Semigroupoid;
+
+    #[test]
+    fn iso_round_trip() {
+        let chars = Iso::new(
+            |s: String| s.chars().collect::<Vec<_>>(),
+            |cs: Vec<char>| cs.into_iter().collect::<String>(),
+        );
+
+        assert_eq!(chars.to("abc".to_string()), vec!['a', 'b', 'c']);
+        assert_eq!(
+            chars.over("abc".to_string(), |mut cs| {
+                cs.reverse();
+                cs
+            }),
+            "cba"
+        );
+
+        let strings = chars.clone().reverse();
+        assert_eq!(strings.to(vec!['o', 'k']), "ok");
+
+        let lens = chars.as_lens();
+        assert_eq!(lens.view(&"hi".to_string()), vec!['h', 'i']);
+        assert_eq!(lens.set("hi".to_string(), vec!['y', 'o']), "yo");
+    }
+
+    #[test]
+    fn iso_compose() {
+        let celsius_to_fahrenheit =
+            Iso::new(|c: f64| c * 9.0 / 5.0 + 32.0, |f| (f - 32.0) * 5.0 / 9.0);
+        let negate = Iso::new(|x: f64| -x, |x| -x);
+
+        let composed = negate.compose(celsius_to_fahrenheit);
+        assert_eq!(composed.to(100.0), -212.0);
+        assert_eq!(composed.from(-212.0), 100.0);
+    }
+}
@@ -0,0 +1,176 @@
+use std::rc::Rc;
+
+use crate::{Category, Fold, Semigroupoid, Traversal};
+
+/// A `Lens` focuses on exactly one part A of a structure S, like a field of a struct:
+/// it can `view` the part and `set` it to a new value, returning the updated structure.
+///
+/// A lens is well-behaved when it obeys the laws:
+/// - `lens.view(&lens.set(s, a)) == a`
+/// - `lens.set(s, lens.view(&s)) == s`
+/// - `lens.set(lens.set(s, a1), a2) == lens.set(s, a2)`
+///
+/// Haskell definition from <https://hackage.haskell.org/package/lens/docs/Control-Lens-Lens.html>
+///
+/// ```haskell
+/// type Lens s t a b = forall f. Functor f => (a -> f b) -> s -> f t
+/// lens :: (s -> a) -> (s -> b -> t) -> Lens s t a b
+/// ```
+pub struct Lens<'a, S, A> {
+    /// Read the part
+    view: Rc<dyn 'a + Fn(&S) -> A>,
+    /// Replace the part
+    set: Rc<dyn 'a + Fn(S, A) -> S>,
+}
+
+impl<'a, S: 'a, A: 'a> Lens<'a, S, A> {
+    /// Constructs a new `Lens` from its getter and setter.
+    pub fn new<V, U>(view: V, set: U) -> Self
+    where
+        V: Fn(&S) -> A + 'a,
+        U: Fn(S, A) -> S + 'a,
+    {
+        Lens {
+            view: Rc::new(view),
+            set: Rc::new(set),
+        }
+    }
+
+    /// Read the part in focus.
+    pub fn view(&self, s: &S) -> A {
+        (self.view)(s)
+    }
+
+    /// Replace the part in focus.
+    pub fn set(&self, s: S, a: A) -> S {
+        (self.set)(s, a)
+    }
+
+    /// Update the part in focus with `f`.
+    pub fn over<F>(&self, s: S, f: F) -> S
+    where
+        F: FnOnce(A) -> A,
+    {
+        let a = f(self.view(&s));
+        self.set(s, a)
+    }
+
+    /// Focus on a part B of the part in focus.
+    pub fn then<B: 'a>(self, inner: Lens<'a, A, B>) -> Lens<'a, S, B> {
+        let outer = self.clone();
+        let inner_view = inner.clone();
+        Lens::new(
+            move |s| inner_view.view(&outer.view(s)),
+            move |s, b| {
+                let a = self.view(&s);
+                self.set(s, inner.set(a, b))
+            },
+        )
+    }
+
+    /// Forget the setter.
+    pub fn as_fold(self) -> Fold<'a, S, A> {
+        Fold::new(move |s| vec![self.view(s)])
+    }
+
+    /// See the lens as a traversal with exactly one target.
+    pub fn as_traversal(self) -> Traversal<'a, S, A> {
+        let lens = self.clone();
+        Traversal::new(move |s| vec![lens.view(s)], move |s, f| self.over(s, f))
+    }
+}
+
+impl<'a, S: Clone + 'a> Lens<'a, S, S> {
+    /// The lens focusing on the whole structure.
+    pub fn id() -> Self {
+        Lens::new(S::clone, |_, s| s)
+    }
+}
+
+impl<'a, S, A> Clone for Lens<'a, S, A> {
+    fn clone(&self) -> Self {
+        Lens {
+            view: self.view.clone(),
+            set: self.set.clone(),
+        }
+    }
+}
+
+/// `f.compose(g)` focuses through `g` first, then through `f`.
+impl<'a, S: 'a> Semigroupoid for Lens<'a, S, S> {
+    fn compose(self, other: Self) -> Self {
+        other.then(self)
+    }
+}
+
+impl<'a, S: Clone + 'a> Category for Lens<'a, S, S> {
+    fn identity(self) -> Self {
+        Lens::id()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Category;
+    use crate::Lens;
+    use crate::Semigroupoid;
+
+    #[derive(Debug, PartialEq, Clone)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Debug, PartialEq, Clone)]
+    struct Circle {
+        center: Point,
+        radius: u32,
+    }
+
+    fn center<'a>() -> Lens<'a, Circle, Point> {
+        Lens::new(
+            |c: &Circle| c.center.clone(),
+            |c, center| Circle { center, ..c },
+        )
+    }
+
+    fn x<'a>() -> Lens<'a, Point, i32> {
+        Lens::new(|p: &Point| p.x, |p, x| Point { x, ..p })
+    }
+
+    #[test]
+    fn lens_view_set_over() {
+        let circle = Circle {
+            center: Point { x: 1, y: 2 },
+            radius: 3,
+        };
+        let center_x = center().then(x());
+
+        assert_eq!(center_x.view(&circle), 1);
+
+        let moved = center_x.over(circle.clone(), |x| x + 41);
+        assert_eq!(moved.center, Point { x: 42, y: 2 });
+        assert_eq!(moved.radius, 3);
+
+        // Lens laws
+        assert_eq!(center_x.view(&center_x.set(circle.clone(), 7)), 7);
+        assert_eq!(center_x.set(circle.clone(), center_x.view(&circle)), circle);
+    }
+
+    #[test]
+    fn lens_category() {
+        // Swap the coordinates of a point, a lens from Point to Point
+        let swapped = Lens::new(
+            |p: &Point| Point { x: p.y, y: p.x },
+            |_, p: Point| Point { x: p.y, y: p.x },
+        );
+        let p = Point { x: 1, y: 2 };
+
+        let twice = swapped.clone().compose(swapped.clone());
+        assert_eq!(twice.view(&p), p);
+
+        let same = swapped.clone().compose(swapped.identity());
+        assert_eq!(same.view(&p), Point { x: 2, y: 1 });
+        assert_eq!(same.set(p, Point { x: 3, y: 4 }), Point { x: 4, y: 3 });
+    }
+}
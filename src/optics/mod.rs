@@ -0,0 +1,18 @@
+/// Optics are first-class getters and setters: values focusing on a part of a bigger structure,
+/// used to read, update or set it without rebuilding the structure by hand.
+/// Each optic composes with optics of the same kind through `then`, and with itself
+/// through `Semigroupoid`/`Category` when it focuses on a part of the same type as the whole.
+mod lens;
+pub use crate::optics::lens::Lens;
+
+mod prism;
+pub use crate::optics::prism::Prism;
+
+mod iso;
+pub use crate::optics::iso::Iso;
+
+mod traversal;
+pub use crate::optics::traversal::Traversal;
+
+mod fold;
+pub use crate::optics::fold::Fold;
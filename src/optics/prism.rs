@@ -0,0 +1,164 @@
+use std::rc::Rc;
+
+use crate::{Category, Semigroupoid, Traversal};
+
+/// A `Prism` focuses on one alternative A of a sum type S, like a variant of an enum:
+/// `preview` extracts the alternative when it is there, and `review` builds an S from an A.
+///
+/// A prism is well-behaved when it obeys the laws:
+/// - `prism.preview(&prism.review(a)) == Some(a)`
+/// - if `prism.preview(&s) == Some(a)` then `prism.review(a) == s`
+///
+/// Haskell definition from <https://hackage.haskell.org/package/lens/docs/Control-Lens-Prism.html>
+///
+/// ```haskell
+/// type Prism s t a b = forall p f. (Choice p, Applicative f) => p a (f b) -> p s (f t)
+/// prism' :: (b -> s) -> (s -> Maybe a) -> Prism s s a b
+/// ```
+#[allow(clippy::type_complexity)]
+pub struct Prism<'a, S, A> {
+    /// Match the alternative
+    preview: Rc<dyn 'a + Fn(&S) -> Option<A>>,
+    /// Build the sum type from the alternative
+    review: Rc<dyn 'a + Fn(A) -> S>,
+}
+
+impl<'a, S: 'a, A: 'a> Prism<'a, S, A> {
+    /// Constructs a new `Prism` from its matcher and builder.
+    pub fn new<P, R>(preview: P, review: R) -> Self
+    where
+        P: Fn(&S) -> Option<A> + 'a,
+        R: Fn(A) -> S + 'a,
+    {
+        Prism {
+            preview: Rc::new(preview),
+            review: Rc::new(review),
+        }
+    }
+
+    /// The alternative in focus, if `s` is that alternative.
+    pub fn preview(&self, s: &S) -> Option<A> {
+        (self.preview)(s)
+    }
+
+    /// Build an S from the alternative.
+    pub fn review(&self, a: A) -> S {
+        (self.review)(a)
+    }
+
+    /// Update the alternative in focus with `f`, leaving the other alternatives untouched.
+    pub fn over<F>(&self, s: S, f: F) -> S
+    where
+        F: FnOnce(A) -> A,
+    {
+        match self.preview(&s) {
+            Some(a) => self.review(f(a)),
+            None => s,
+        }
+    }
+
+    /// Focus on an alternative B of the alternative in focus.
+    pub fn then<B: 'a>(self, inner: Prism<'a, A, B>) -> Prism<'a, S, B> {
+        let outer = self.clone();
+        let inner_review = inner.clone();
+        Prism::new(
+            move |s| outer.preview(s).and_then(|a| inner.preview(&a)),
+            move |b| self.review(inner_review.review(b)),
+        )
+    }
+
+    /// See the prism as a traversal with at most one target.
+    pub fn as_traversal(self) -> Traversal<'a, S, A> {
+        let prism = self.clone();
+        Traversal::new(
+            move |s| prism.preview(s).into_iter().collect(),
+            move |s, f| self.over(s, f),
+        )
+    }
+}
+
+impl<'a, S: Clone + 'a> Prism<'a, S, S> {
+    /// The prism always matching the whole structure.
+    pub fn id() -> Self {
+        Prism::new(|s: &S| Some(s.clone()), |s| s)
+    }
+}
+
+impl<'a, S, A> Clone for Prism<'a, S, A> {
+    fn clone(&self) -> Self {
+        Prism {
+            preview: self.preview.clone(),
+            review: self.review.clone(),
+        }
+    }
+}
+
+/// `f.compose(g)` matches `g` first, then `f`.
+impl<'a, S: 'a> Semigroupoid for Prism<'a, S, S> {
+    fn compose(self, other: Self) -> Self {
+        other.then(self)
+    }
+}
+
+impl<'a, S: Clone + 'a> Category for Prism<'a, S, S> {
+    fn identity(self) -> Self {
+        Prism::id()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Prism;
+
+    #[derive(Debug, PartialEq, Clone)]
+    enum Json {
+        Null,
+        Number(f64),
+        Array(Vec<Json>),
+    }
+
+    fn number<'a>() -> Prism<'a, Json, f64> {
+        Prism::new(
+            |j: &Json| match j {
+                Json::Number(n) => Some(*n),
+                _ => None,
+            },
+            Json::Number,
+        )
+    }
+
+    #[test]
+    fn prism_preview_review() {
+        assert_eq!(number().preview(&Json::Number(4.2)), Some(4.2));
+        assert_eq!(number().preview(&Json::Null), None);
+        assert_eq!(number().review(4.2), Json::Number(4.2));
+
+        assert_eq!(
+            number().over(Json::Number(41.0), |n| n + 1.0),
+            Json::Number(42.0)
+        );
+        assert_eq!(number().over(Json::Null, |n| n + 1.0), Json::Null);
+    }
+
+    #[test]
+    fn prism_then() {
+        let singleton = Prism::new(
+            |j: &Json| match j {
+                Json::Array(items) if items.len() == 1 => Some(items[0].clone()),
+                _ => None,
+            },
+            |j| Json::Array(vec![j]),
+        );
+        let single_number = singleton.then(number());
+
+        assert_eq!(
+            single_number.preview(&Json::Array(vec![Json::Number(1.0)])),
+            Some(1.0)
+        );
+        assert_eq!(single_number.preview(&Json::Array(vec![Json::Null])), None);
+        assert_eq!(
+            single_number.review(2.0),
+            Json::Array(vec![Json::Number(2.0)])
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::rc::Rc;
+
+use crate::{Category, Fold, Identity, Semigroupoid, Traversable, Writer};
+
+/// A `Traversal` focuses on zero or more parts A of a structure S, like the elements of a collection:
+/// it can read all of them with `to_vec` and update all of them at once with `over`.
+///
+/// `Traversal::traversed` focuses on the elements of any `Traversable` container.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/lens/docs/Control-Lens-Traversal.html>
+///
+/// ```haskell
+/// type Traversal s t a b = forall f. Applicative f => (a -> f b) -> s -> f t
+/// traversed :: Traversable f => Traversal (f a) (f b) a b
+/// ```
+#[allow(clippy::type_complexity)]
+pub struct Traversal<'a, S, A> {
+    /// Collect the parts, from left to right
+    to_vec: Rc<dyn 'a + Fn(&S) -> Vec<A>>,
+    /// Update every part
+    over: Rc<dyn 'a + Fn(S, &dyn Fn(A) -> A) -> S>,
+}
+
+impl<'a, S: 'a, A: 'a> Traversal<'a, S, A> {
+    /// Constructs a new `Traversal` from the functions collecting and updating the parts.
+    pub fn new<T, O>(to_vec: T, over: O) -> Self
+    where
+        T: Fn(&S) -> Vec<A> + 'a,
+        O: Fn(S, &dyn Fn(A) -> A) -> S + 'a,
+    {
+        Traversal {
+            to_vec: Rc::new(to_vec),
+            over: Rc::new(over),
+        }
+    }
+
+    /// All the parts in focus, from left to right.
+    pub fn to_vec(&self, s: &S) -> Vec<A> {
+        (self.to_vec)(s)
+    }
+
+    /// Update every part in focus with `f`.
+    pub fn over<F>(&self, s: S, f: F) -> S
+    where
+        F: Fn(A) -> A,
+    {
+        (self.over)(s, &f)
+    }
+
+    /// Replace every part in focus with `a`.
+    pub fn set(&self, s: S, a: A) -> S
+    where
+        A: Clone,
+    {
+        self.over(s, |_| a.clone())
+    }
+
+    /// Focus on the parts B of each part in focus.
+    pub fn then<B: 'a>(self, inner: Traversal<'a, A, B>) -> Traversal<'a, S, B> {
+        let outer = self.clone();
+        let inner_over = inner.clone();
+        Traversal::new(
+            move |s| {
+                outer
+                    .to_vec(s)
+                    .iter()
+                    .flat_map(|a| inner.to_vec(a))
+                    .collect()
+            },
+            move |s, f| self.over(s, |a| inner_over.over(a, f)),
+        )
+    }
+
+    /// Forget the setter.
+    pub fn as_fold(self) -> Fold<'a, S, A> {
+        Fold::new(move |s| self.to_vec(s))
+    }
+}
+
+impl<'a, T, A> Traversal<'a, T, A>
+where
+    T: Traversable<'a, Unwrapped = A, Wrapped<A> = T> + Clone + 'a,
+    A: Clone + 'a,
+{
+    /// The traversal focusing on every element of a container.
+    pub fn traversed() -> Self {
+        Traversal::new(
+            // The parts are collected in order, as the log of a `Writer`
+            |t: &T| {
+                let (_, parts) = t
+                    .clone()
+                    .traverse::<_, A, Writer<T, Vec<A>>>(|a| {
+                        Writer::new(a.clone(), vec![a.clone()])
+                    })
+                    .execute();
+                parts
+            },
+            // `Identity` is used as a trivial effect: the traversal always succeeds
+            |t, f| {
+                t.traverse::<_, A, Identity<T>>(|a| Identity::new(f(a.clone())))
+                    .execute()
+            },
+        )
+    }
+}
+
+impl<'a, S: 'a> Traversal<'a, S, S> {
+    /// The traversal focusing on the whole structure.
+    pub fn id() -> Self
+    where
+        S: Clone,
+    {
+        Traversal::new(|s: &S| vec![s.clone()], |s, f| f(s))
+    }
+}
+
+impl<'a, S, A> Clone for Traversal<'a, S, A> {
+    fn clone(&self) -> Self {
+        Traversal {
+            to_vec: self.to_vec.clone(),
+            over: self.over.clone(),
+        }
+    }
+}
+
+/// `f.compose(g)` traverses with `g` first, then with `f`.
+impl<'a, S: 'a> Semigroupoid for Traversal<'a, S, S> {
+    fn compose(self, other: Self) -> Self {
+        other.then(self)
+    }
+}
+
+impl<'a, S: Clone + 'a> Category for Traversal<'a, S, S> {
+    fn identity(self) -> Self {
+        Traversal::id()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Lens;
+    use crate::Traversal;
+
+    #[test]
+    fn traversal_traversed() {
+        let each = Traversal::<Vec<i32>, i32>::traversed();
+
+        assert_eq!(each.to_vec(&vec![1, 2, 3]), vec![1, 2, 3]);
+        assert_eq!(each.over(vec![1, 2, 3], |n| n * 10), vec![10, 20, 30]);
+        assert_eq!(each.set(vec![1, 2], 0), vec![0, 0]);
+    }
+
+    #[test]
+    fn traversal_then() {
+        #[derive(Debug, PartialEq, Clone)]
+        struct Team {
+            name: String,
+            scores: Vec<i32>,
+        }
+
+        let scores = Lens::new(
+            |t: &Team| t.scores.clone(),
+            |t, scores| Team { scores, ..t },
+        );
+        let teams_each = Traversal::<Vec<Team>, Team>::traversed();
+        let all_scores = teams_each.then(scores.as_traversal().then(Traversal::traversed()));
+
+        let teams = vec![
+            Team {
+                name: "red".to_string(),
+                scores: vec![1, 2],
+            },
+            Team {
+                name: "blue".to_string(),
+                scores: vec![3],
+            },
+        ];
+
+        assert_eq!(all_scores.to_vec(&teams), vec![1, 2, 3]);
+
+        let doubled = all_scores.over(teams, |n| n * 2);
+        assert_eq!(doubled[0].scores, vec![2, 4]);
+        assert_eq!(doubled[1].scores, vec![6]);
+    }
+}
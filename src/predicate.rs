@@ -0,0 +1,160 @@
+use std::{convert::Infallible, rc::Rc};
+
+use crate::{Contravariant, Decidable, Divisible, Monoid, Semigroup};
+
+/// A condition on values of type A.
+///
+/// Predicates are combined with `and`, `or` and `not`, and with `Semigroup`/`Monoid` as a conjunction
+/// (`mempty` holds for every value). `contramap` checks the condition on a part of a bigger value,
+/// e.g. `is_adult.contramap(|p: &Person| p.age)`.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/base/docs/Data-Functor-Contravariant.html>
+///
+/// ```haskell
+/// newtype Predicate a = Predicate { getPredicate :: a -> Bool }
+/// ```
+pub struct Predicate<'a, A> {
+    runner: Rc<dyn 'a + Fn(&A) -> bool>,
+}
+
+impl<'a, A: 'a> Predicate<'a, A> {
+    /// Constructs a new `Predicate` from the condition it checks.
+    pub fn new<F>(runner: F) -> Self
+    where
+        F: Fn(&A) -> bool + 'a,
+    {
+        Predicate {
+            runner: Rc::new(runner),
+        }
+    }
+
+    /// Check the condition on `a`.
+    pub fn test(&self, a: &A) -> bool {
+        (self.runner)(a)
+    }
+
+    /// Borrow the predicate as a plain function, e.g. for `Iterator::filter`.
+    pub fn as_fn(&self) -> impl Fn(&A) -> bool + '_ {
+        |a| self.test(a)
+    }
+
+    /// Holds when both conditions hold.
+    pub fn and(self, other: Self) -> Self {
+        Predicate::new(move |a| self.test(a) && other.test(a))
+    }
+
+    /// Holds when any of the conditions holds.
+    pub fn or(self, other: Self) -> Self {
+        Predicate::new(move |a| self.test(a) || other.test(a))
+    }
+
+    /// Holds when the condition doesn't.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        Predicate::new(move |a| !self.test(a))
+    }
+}
+
+impl<'a, A> Clone for Predicate<'a, A> {
+    fn clone(&self) -> Self {
+        Predicate {
+            runner: self.runner.clone(),
+        }
+    }
+}
+
+impl<'a, A: 'a> Semigroup for Predicate<'a, A> {
+    fn mappend(self, other: Self) -> Self {
+        self.and(other)
+    }
+}
+
+impl<'a, A: 'a> Monoid for Predicate<'a, A> {
+    fn mempty() -> Self {
+        Predicate::new(|_| true)
+    }
+}
+
+impl<'a, A: 'a> Contravariant<'a> for Predicate<'a, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Predicate<'a, B>;
+
+    fn contramap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(&B) -> Self::Unwrapped + 'a,
+    {
+        Predicate::new(move |b| self.test(&f(b)))
+    }
+}
+
+impl<'a, A: 'a> Divisible<'a> for Predicate<'a, A> {
+    fn divide<F, B: 'a, C: 'a>(f: F, fb: Predicate<'a, B>, fc: Predicate<'a, C>) -> Self
+    where
+        F: Fn(&A) -> (B, C) + 'a,
+    {
+        Predicate::new(move |a| {
+            let (b, c) = f(a);
+            fb.test(&b) && fc.test(&c)
+        })
+    }
+
+    fn conquer() -> Self {
+        Predicate::mempty()
+    }
+}
+
+impl<'a, A: 'a> Decidable<'a> for Predicate<'a, A> {
+    fn choose<F, B: 'a, C: 'a>(f: F, fb: Predicate<'a, B>, fc: Predicate<'a, C>) -> Self
+    where
+        F: Fn(&A) -> Result<B, C> + 'a,
+    {
+        Predicate::new(move |a| match f(a) {
+            Ok(b) => fb.test(&b),
+            Err(c) => fc.test(&c),
+        })
+    }
+
+    fn lose<F>(f: F) -> Self
+    where
+        F: Fn(&A) -> Infallible + 'a,
+    {
+        Predicate::new(move |a| match f(a) {})
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Divisible;
+    use crate::Monoid;
+    use crate::Predicate;
+    use crate::Semigroup;
+
+    #[test]
+    fn predicate_combinators() {
+        let even = Predicate::new(|n: &i32| n % 2 == 0);
+        let positive = Predicate::new(|n: &i32| *n > 0);
+
+        let both = even.clone().and(positive.clone());
+        assert_eq!((-2..=2).filter(both.as_fn()).collect::<Vec<_>>(), vec![2]);
+
+        let any = even.clone().or(positive);
+        assert_eq!(
+            (-2..=2).filter(any.as_fn()).collect::<Vec<_>>(),
+            vec![-2, 0, 1, 2]
+        );
+
+        assert!(even.clone().not().test(&3));
+        assert!(Predicate::mempty().mappend(even).test(&4));
+    }
+
+    #[test]
+    fn predicate_divide() {
+        let in_range = Predicate::new(|n: &i32| (0..10).contains(n));
+        let point: Predicate<(i32, i32)> =
+            Divisible::divide(|&(x, y)| (x, y), in_range.clone(), in_range);
+
+        assert!(point.test(&(1, 9)));
+        assert!(!point.test(&(1, 10)));
+    }
+}
@@ -0,0 +1,408 @@
+use std::{
+    cell::Cell,
+    ops::ControlFlow,
+    rc::Rc,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::{Applicative, Apply, Functor, Monad, MonadRec};
+
+/// The channel through which an interpreter answers an instruction with a value of type T.
+///
+/// Rust has no GADTs, so instead of indexing the instruction enum by the result type,
+/// every variant carries the `Reply` of its own result type, e.g.
+///
+/// ```ignore
+/// enum KeyVal {
+///     Get(String, Reply<Option<String>>),
+///     Put(String, String, Reply<()>),
+/// }
+/// ```
+pub struct Reply<T> {
+    id: u64,
+    slot: Rc<Cell<Option<T>>>,
+}
+
+/// Proof that an instruction has been answered: the only way to get one is `Reply::answer`.
+///
+/// An `Answer` is branded with the `Reply` it comes from, and resuming an instruction
+/// with the `Answer` of another one panics right away, instead of leaving its result unset.
+pub struct Answer {
+    id: u64,
+}
+
+/// Source of the brands shared by a `Reply` and its `Answer`
+static REPLIES: AtomicU64 = AtomicU64::new(0);
+
+impl<T> Reply<T> {
+    /// Answer the instruction with `value`.
+    pub fn answer(self, value: T) -> Answer {
+        self.slot.set(Some(value));
+        Answer { id: self.id }
+    }
+}
+
+/// The continuation of an instruction: the rest of the program, once the instruction is answered
+///
+/// # Panics
+///
+/// Calling it with the `Answer` of any other instruction than its own panics.
+pub type Next<'a, I, A> = Box<dyn 'a + FnOnce(Answer) -> Program<'a, I, A>>;
+
+/// The first step of a `Program`: either it is done, or it runs an instruction before going on.
+pub enum ProgramView<'a, I, A> {
+    Return(A),
+    Instr(I, Next<'a, I, A>),
+}
+
+/// The "freer" (or operational) monad: a `Program` is a sequence of instructions of type I, returning an A.
+///
+/// Unlike `Free`, the instructions don't need to be a `FunctorOnce`: every instruction is paired with
+/// its continuation by `singleton`, so a DSL is just an enum of instructions (carrying their `Reply` channels)
+/// and an interpreter, either a function run by `interpret` or a hand-written loop over `view`.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/operational/docs/Control-Monad-Operational.html>
+///
+/// ```haskell
+/// data ProgramView instr a where
+///   Return :: a -> ProgramView instr a
+///   (:>>=) :: instr b -> (b -> Program instr a) -> ProgramView instr a
+/// ```
+pub struct Program<'a, I, A> {
+    steps: Steps<'a, I>,
+    /// Reads the final value, once every step has run
+    result: Box<dyn 'a + FnOnce() -> A>,
+}
+
+/// The instructions of a `Program`, without its result: as they don't depend on the result type,
+/// `view` can re-associate left-nested binds one step at a time, instead of recursing through them.
+enum Steps<'a, I> {
+    Done,
+    Instr(I, Box<dyn 'a + FnOnce(Answer) -> Steps<'a, I>>),
+    Then(Box<Steps<'a, I>>, Box<dyn 'a + FnOnce() -> Steps<'a, I>>),
+}
+
+impl<'a, I: 'a, A: 'a> Program<'a, I, A> {
+    /// A program without instructions.
+    pub fn pure(value: A) -> Self {
+        Program {
+            steps: Steps::Done,
+            result: Box::new(move || value),
+        }
+    }
+
+    /// A program made of a single instruction, built by `instruction` from the channel of its result.
+    ///
+    /// # Panics
+    ///
+    /// The instruction is resumed (by `interpret`, or by calling the `Next` handed out by `view`)
+    /// with an `Answer`: it panics if that `Answer` comes from the `Reply` of another instruction.
+    /// The brand can't be checked by the compiler, as every instruction of I shares the same `Answer` type.
+    pub fn singleton<F>(instruction: F) -> Self
+    where
+        F: FnOnce(Reply<A>) -> I,
+    {
+        let id = REPLIES.fetch_add(1, Ordering::Relaxed);
+        let slot = Rc::new(Cell::new(None));
+        let reply = Reply {
+            id,
+            slot: slot.clone(),
+        };
+
+        Program {
+            steps: Steps::Instr(
+                instruction(reply),
+                Box::new(move |answer: Answer| {
+                    assert_eq!(
+                        answer.id, id,
+                        "an instruction is resumed with the Answer of its own Reply"
+                    );
+                    Steps::Done
+                }),
+            ),
+            // The brand check above guarantees the slot is set once the step is done
+            result: Box::new(move || slot.take().expect("an answered instruction has a value")),
+        }
+    }
+
+    /// Look at the first step of the program.
+    pub fn view(self) -> ProgramView<'a, I, A> {
+        let result = self.result;
+        let mut steps = self.steps;
+        loop {
+            steps = match steps {
+                Steps::Done => return ProgramView::Return(result()),
+                Steps::Instr(instruction, next) => {
+                    return ProgramView::Instr(
+                        instruction,
+                        Box::new(move |answer| Program {
+                            steps: next(answer),
+                            result,
+                        }),
+                    )
+                }
+                Steps::Then(first, rest) => match *first {
+                    Steps::Done => rest(),
+                    Steps::Instr(instruction, next) => {
+                        return ProgramView::Instr(
+                            instruction,
+                            Box::new(move |answer| Program {
+                                steps: Steps::Then(Box::new(next(answer)), rest),
+                                result,
+                            }),
+                        )
+                    }
+                    // (m >>= f) >>= g = m >>= (\x -> f x >>= g)
+                    Steps::Then(first, inner) => Steps::Then(
+                        first,
+                        Box::new(move || Steps::Then(Box::new(inner()), rest)),
+                    ),
+                },
+            }
+        }
+    }
+
+    /// Run the program in the monad M, where `step` runs each instruction.
+    ///
+    /// The program is run by `tail_rec_m`, one instruction at a time:
+    /// as long as M is stack-safe, so is the interpreter, whatever the length of the program.
+    ///
+    /// # Panics
+    ///
+    /// If `step` answers an instruction with the `Reply` of another one (see `singleton`).
+    pub fn interpret<M, F>(self, mut step: F) -> M::Wrapped<A>
+    where
+        M: MonadRec<'a, Unwrapped = Answer>,
+        F: FnMut(I) -> M + 'a,
+    {
+        M::tail_rec_m(self, move |program| match program.view() {
+            ProgramView::Return(a) => M::of(ControlFlow::Break(a)),
+            ProgramView::Instr(instruction, next) => {
+                step(instruction).bind::<_, ControlFlow<A, Program<'a, I, A>>>(move |answer| {
+                    M::of(ControlFlow::Continue(next(answer)))
+                })
+            }
+        })
+    }
+}
+
+impl<'a, I: 'a, A: 'a> Functor<'a> for Program<'a, I, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Program<'a, I, B>;
+
+    fn fmap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(Self::Unwrapped) -> B + 'a,
+    {
+        self.bind(move |a| Program::pure(f(a)))
+    }
+}
+
+impl<'a, I: 'a, A: 'a> Apply<'a> for Program<'a, I, A> {
+    fn ap<F, B: 'a>(self, f: Self::Wrapped<F>) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> B + 'a,
+    {
+        self.lift_a2(f, |a, f| f(a))
+    }
+
+    fn lift_a2<F, B: 'a, C: 'a>(self, b: Self::Wrapped<B>, f: F) -> Self::Wrapped<C>
+    where
+        F: FnOnce(Self::Unwrapped, B) -> C + 'a,
+    {
+        self.bind(move |a1| b.bind(move |a2| Program::pure(f(a1, a2))))
+    }
+}
+
+impl<'a, I: 'a, A: 'a> Applicative<'a> for Program<'a, I, A> {
+    fn of(value: Self::Unwrapped) -> Self::Wrapped<Self::Unwrapped> {
+        Program::pure(value)
+    }
+}
+
+impl<'a, I: 'a, A: 'a> Monad<'a> for Program<'a, I, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Program<'a, I, B>;
+
+    fn bind<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> Self::Wrapped<B> + 'a,
+    {
+        // The continuation is queued after the steps of this program, without looking into them:
+        // the value of the program built by f is stored as a last step, so reading it never recurses
+        let slot = Rc::new(Cell::new(None));
+        let store = slot.clone();
+        let result = self.result;
+
+        Program {
+            steps: Steps::Then(
+                Box::new(self.steps),
+                Box::new(move || {
+                    let next = f(result());
+                    let result = next.result;
+                    Steps::Then(
+                        Box::new(next.steps),
+                        Box::new(move || {
+                            store.set(Some(result()));
+                            Steps::Done
+                        }),
+                    )
+                }),
+            ),
+            result: Box::new(move || {
+                slot.take()
+                    .expect("the steps of a program run before its result")
+            }),
+        }
+    }
+
+    fn of<T: 'a>(value: T) -> Self::Wrapped<T> {
+        Program::pure(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use crate::m;
+    use crate::Answer;
+    use crate::Apply;
+    use crate::Functor;
+    use crate::Monad;
+    use crate::Program;
+    use crate::ProgramView;
+    use crate::Reply;
+    use crate::State;
+    use crate::Writer;
+
+    enum KeyVal {
+        Get(String, Reply<Option<String>>),
+        Put(String, String, Reply<()>),
+    }
+
+    type KV<'a, A> = Program<'a, KeyVal, A>;
+
+    fn get<'a>(key: &str) -> KV<'a, Option<String>> {
+        Program::singleton(|reply| KeyVal::Get(key.into(), reply))
+    }
+
+    fn put<'a>(key: &str, value: &str) -> KV<'a, ()> {
+        Program::singleton(|reply| KeyVal::Put(key.into(), value.into(), reply))
+    }
+
+    fn prog<'a>() -> KV<'a, String> {
+        m! {
+            put("1", "ue");
+            put("2", "my love");
+            a <- get("2");
+            b <- get("3");
+            Program::pure(format!("{:?} {:?}", a, b))
+        }
+    }
+
+    type Store = HashMap<String, String>;
+
+    fn in_memory<'a>(instruction: KeyVal) -> State<'a, Store, Answer> {
+        State::new(move |mut store: Store| match instruction {
+            KeyVal::Get(k, reply) => (reply.answer(store.get(&k).cloned()), store),
+            KeyVal::Put(k, v, reply) => {
+                store.insert(k, v);
+                (reply.answer(()), store)
+            }
+        })
+    }
+
+    #[test]
+    fn program_interpret_state() {
+        let (result, store) = prog().interpret(in_memory).execute(HashMap::new());
+
+        assert_eq!(result, "Some(\"my love\") None");
+        assert_eq!(store.len(), 2);
+    }
+
+    #[test]
+    fn program_interpret_writer() {
+        let (result, log) = prog()
+            .interpret(|instruction| match instruction {
+                KeyVal::Get(k, reply) => {
+                    Writer::new(reply.answer(None), vec![format!("get {}", k)])
+                }
+                KeyVal::Put(k, v, reply) => {
+                    Writer::new(reply.answer(()), vec![format!("put {}={}", k, v)])
+                }
+            })
+            .execute();
+
+        assert_eq!(result, "None None");
+        assert_eq!(log, vec!["put 1=ue", "put 2=my love", "get 2", "get 3"]);
+    }
+
+    #[test]
+    fn program_view() {
+        // A hand-written interpreter, which only allows reading
+        fn read_only(program: KV<String>, config: &Store) -> Result<String, String> {
+            let mut program = program;
+            loop {
+                match program.view() {
+                    ProgramView::Return(a) => return Ok(a),
+                    ProgramView::Instr(KeyVal::Get(k, reply), next) => {
+                        program = next(reply.answer(config.get(&k).cloned()))
+                    }
+                    ProgramView::Instr(KeyVal::Put(k, _, _), _) => {
+                        return Err(format!("cannot put {}", k))
+                    }
+                }
+            }
+        }
+
+        let config = HashMap::from([("2".to_string(), "two".to_string())]);
+
+        let reads = get("2").lift_a2(get("3"), |a, b| format!("{:?} {:?}", a, b));
+        assert_eq!(
+            read_only(reads, &config),
+            Ok("Some(\"two\") None".to_string())
+        );
+        assert_eq!(read_only(prog(), &config), Err("cannot put 1".to_string()));
+    }
+
+    #[test]
+    fn program_fmap_long_loop() {
+        fn count<'a>(n: u32) -> KV<'a, u32> {
+            m! {
+                current <- get("n").fmap(|v| v.map_or(0, |v| v.parse::<u32>().unwrap()));
+                put("n", &(current + 1).to_string());
+                if n == 1 { Program::pure(current + 1) } else { count(n - 1) }
+            }
+        }
+
+        let (result, _) = count(100_000).interpret(in_memory).execute(HashMap::new());
+        assert_eq!(result, 100_000);
+    }
+
+    #[test]
+    fn program_left_nested_binds() {
+        let prog = (0..100_000).fold(Program::pure(0), |prog: KV<u32>, _| {
+            prog.bind(|n| put("n", &n.to_string()).fmap(move |_| n + 1))
+        });
+
+        let (result, store) = prog.interpret(in_memory).execute(HashMap::new());
+        assert_eq!(result, 100_000);
+        assert_eq!(store["n"], "99999");
+    }
+
+    #[test]
+    #[should_panic(expected = "an instruction is resumed with the Answer of its own Reply")]
+    fn program_foreign_answer() {
+        let other = match get("other").view() {
+            ProgramView::Instr(KeyVal::Get(_, reply), _) => reply,
+            _ => unreachable!(),
+        };
+
+        if let ProgramView::Instr(_, next) = get("n").view() {
+            next(other.answer(None));
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{Applicative, Apply, Functor, Monad};
+
+/// `Reader` describes a wrapped function that can be used to pass around some
+/// read-only environment (configuration, dependencies, ...).
+/// The environment is threaded implicitly through the whole computation,
+/// so there is no need to pass it around explicitly from function to function.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/mtl/docs/Control-Monad-Reader.html>
+///
+/// ```haskell
+/// newtype Reader r a = Reader { runReader :: r -> a }
+/// ```
+pub struct Reader<'a, R, A> {
+    /// The function which reads from the environment
+    pub runner: Box<dyn 'a + FnOnce(&R) -> A>,
+}
+
+impl<'a, R: 'a, A: 'a> Reader<'a, R, A> {
+    /// Constructs a new `Reader` by passing in the function which reads the environment
+    pub fn new<F>(runner: F) -> Self
+    where
+        F: FnOnce(&R) -> A + 'a,
+    {
+        Self {
+            runner: Box::new(runner),
+        }
+    }
+
+    /// Run a `Reader` by passing in the environment to actualy run the enclosed runner.
+    pub fn execute(self, env: &R) -> A {
+        (self.runner)(env)
+    }
+
+    /// Retrieve a value which is a function of the environment.
+    pub fn asks<F>(f: F) -> Self
+    where
+        F: FnOnce(&R) -> A + 'a,
+    {
+        Reader::new(f)
+    }
+
+    /// Execute a computation in a modified environment.
+    pub fn local<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&R) -> R + 'a,
+    {
+        Reader::new(move |r| (self.runner)(&f(r)))
+    }
+}
+
+impl<'a, R: 'a + Clone> Reader<'a, R, R> {
+    /// Retrieve the environment.
+    pub fn ask() -> Self {
+        Reader::new(|r: &R| r.clone())
+    }
+}
+
+impl<'a, R: 'a, A: 'a> Functor<'a> for Reader<'a, R, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Reader<'a, R, B>;
+
+    fn fmap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> B + 'a,
+    {
+        Reader {
+            runner: Box::new(move |r| f((self.runner)(r))),
+        }
+    }
+}
+
+impl<'a, R: 'a, A: 'a> Apply<'a> for Reader<'a, R, A> {
+    fn ap<F, B: 'a>(self, f: Self::Wrapped<F>) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> B + 'a,
+    {
+        Reader {
+            runner: Box::new(move |r| {
+                let a = (self.runner)(r);
+                let f = (f.runner)(r);
+                f(a)
+            }),
+        }
+    }
+
+    fn lift_a2<F, B: 'a, C: 'a>(self, b: Self::Wrapped<B>, f: F) -> Self::Wrapped<C>
+    where
+        F: FnOnce(Self::Unwrapped, B) -> C + 'a,
+    {
+        Reader {
+            runner: Box::new(move |r| {
+                let a1 = (self.runner)(r);
+                let a2 = (b.runner)(r);
+                f(a1, a2)
+            }),
+        }
+    }
+}
+
+impl<'a, R: 'a, A: 'a> Applicative<'a> for Reader<'a, R, A> {
+    fn of(value: Self::Unwrapped) -> Self::Wrapped<Self::Unwrapped> {
+        Reader::new(|_| value)
+    }
+}
+
+impl<'a, R: 'a, A: 'a> Monad<'a> for Reader<'a, R, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Reader<'a, R, B>;
+
+    fn bind<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> Self::Wrapped<B> + 'a,
+    {
+        Reader {
+            runner: Box::new(move |r| {
+                let a1 = (self.runner)(r);
+                (f(a1).runner)(r)
+            }),
+        }
+    }
+
+    fn of<T: 'a>(value: T) -> Self::Wrapped<T> {
+        Reader::new(|_| value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::m;
+    use crate::Apply;
+    use crate::Functor;
+    use crate::Monad;
+    use crate::Reader;
+
+    struct Config {
+        name: String,
+        verbose: bool,
+    }
+
+    #[test]
+    fn reader_fmap() {
+        let r = Reader::new(|env: &i32| env + 2);
+
+        let r2 = r.fmap(|a| format!("{}", a));
+        assert_eq!(r2.execute(&40), "42".to_string());
+    }
+
+    #[test]
+    fn reader_ap() {
+        let r = Reader::new(|env: &i32| env + 2);
+        let f = Reader::new(|env: &i32| {
+            let env = *env;
+            move |x| x * env
+        });
+
+        assert_eq!(r.ap(f).execute(&10), 120);
+    }
+
+    #[test]
+    fn reader_lifta2() {
+        let r1 = Reader::new(|env: &String| env.len());
+        let r2 = Reader::new(|env: &String| env.to_uppercase());
+
+        let res = r1.lift_a2(r2, |x, y| format!("{}{}", y, x));
+
+        assert_eq!(res.execute(&"foo".to_string()), "FOO3".to_string());
+    }
+
+    #[test]
+    fn reader_bind() {
+        let r = Reader::new(|env: &i32| env + 1);
+
+        let r2 = r.bind(|a| Reader::new(move |env: &i32| a + env));
+
+        assert_eq!(r2.execute(&20), 41);
+    }
+
+    #[test]
+    fn reader_ask_asks_local() {
+        let greet = |suffix: &'static str| {
+            m! {
+                name <- Reader::asks(|c: &Config| c.name.clone());
+                verbose <- Reader::asks(|c: &Config| c.verbose);
+                Reader::new(move |_| if verbose {
+                    format!("Hello {}{}", name, suffix)
+                } else {
+                    name
+                })
+            }
+        };
+
+        let config = Config {
+            name: "Algar".into(),
+            verbose: false,
+        };
+
+        assert_eq!(greet("!").execute(&config), "Algar");
+        assert_eq!(
+            greet("!")
+                .local(|c| Config {
+                    name: c.name.clone(),
+                    verbose: true,
+                })
+                .execute(&config),
+            "Hello Algar!"
+        );
+
+        let r = m! {
+            env <- Reader::ask();
+            Reader::new(move |e: &i32| env + e)
+        };
+        assert_eq!(r.execute(&21), 42);
+    }
+}
@@ -0,0 +1,247 @@
+use crate::{Applicative, Apply, Functor, Monad, Monoid};
+
+/// `RWS` combines `Reader`, `Writer` and `State` in a single monad: the computation
+/// reads from an environment (R), appends to a log (W) and passes around a state (S).
+///
+/// Compared to stacking the three transformers by hand, there is only one boxed
+/// function per computation and no `lift` is ever needed.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/mtl/docs/Control-Monad-RWS-Lazy.html>
+///
+/// ```haskell
+/// newtype RWS r w s a = RWS { runRWS :: r -> s -> (a, s, w) }
+/// ```
+#[allow(clippy::type_complexity)]
+pub struct RWS<'a, R, W: Monoid, S, A> {
+    /// The function which reads the environment, uses and updates the state and writes the log
+    pub runner: Box<dyn 'a + FnOnce(&R, S) -> (A, S, W)>,
+}
+
+impl<'a, R: 'a, W: 'a + Monoid, S: 'a, A: 'a> RWS<'a, R, W, S, A> {
+    /// Constructs a new `RWS` by passing in the function which reads the environment,
+    /// uses and updates the state and writes the log
+    pub fn new<F>(runner: F) -> Self
+    where
+        F: FnOnce(&R, S) -> (A, S, W) + 'a,
+    {
+        Self {
+            runner: Box::new(runner),
+        }
+    }
+
+    /// Run a `RWS` by passing in the environment and some initial state,
+    /// returning the final value, the final state and the log.
+    pub fn run(self, env: &R, state: S) -> (A, S, W) {
+        (self.runner)(env, state)
+    }
+
+    /// Run a `RWS` and return the final value and the log, discarding the final state.
+    pub fn eval(self, env: &R, state: S) -> (A, W) {
+        let (a, _, w) = self.run(env, state);
+        (a, w)
+    }
+
+    /// Run a `RWS` and return the final state and the log, discarding the final value.
+    pub fn exec(self, env: &R, state: S) -> (S, W) {
+        let (_, s, w) = self.run(env, state);
+        (s, w)
+    }
+
+    /// Retrieve a value which is a function of the environment.
+    pub fn asks<F>(f: F) -> Self
+    where
+        F: FnOnce(&R) -> A + 'a,
+    {
+        RWS::new(|r, s| (f(r), s, W::mempty()))
+    }
+
+    /// Retrieve a value which is a function of the current state.
+    pub fn gets<F>(f: F) -> Self
+    where
+        F: FnOnce(&S) -> A + 'a,
+    {
+        RWS::new(|_, s| (f(&s), s, W::mempty()))
+    }
+
+    /// Execute a computation in a modified environment.
+    pub fn local<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&R) -> R + 'a,
+    {
+        RWS::new(move |r, s| (self.runner)(&f(r), s))
+    }
+}
+
+impl<'a, R: 'a + Clone, W: 'a + Monoid, S: 'a> RWS<'a, R, W, S, R> {
+    /// Retrieve the environment.
+    pub fn ask() -> Self {
+        RWS::new(|r: &R, s| (r.clone(), s, W::mempty()))
+    }
+}
+
+impl<'a, R: 'a, W: 'a + Monoid, S: 'a + Clone> RWS<'a, R, W, S, S> {
+    /// Retrieve the current state.
+    pub fn get() -> Self {
+        RWS::new(|_, s: S| (s.clone(), s, W::mempty()))
+    }
+}
+
+impl<'a, R: 'a, W: 'a + Monoid, S: 'a> RWS<'a, R, W, S, ()> {
+    /// Append `log` to the output.
+    pub fn tell(log: W) -> Self {
+        RWS::new(|_, s| ((), s, log))
+    }
+
+    /// Replace the state.
+    pub fn put(state: S) -> Self {
+        RWS::new(|_, _| ((), state, W::mempty()))
+    }
+
+    /// Update the state by applying `f`.
+    pub fn modify<F>(f: F) -> Self
+    where
+        F: FnOnce(S) -> S + 'a,
+    {
+        RWS::new(|_, s| ((), f(s), W::mempty()))
+    }
+}
+
+impl<'a, R: 'a, W: 'a + Monoid, S: 'a, A: 'a> Functor<'a> for RWS<'a, R, W, S, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = RWS<'a, R, W, S, B>;
+
+    fn fmap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> B + 'a,
+    {
+        RWS {
+            runner: Box::new(move |r, s| {
+                let (a1, s1, w1) = (self.runner)(r, s);
+                (f(a1), s1, w1)
+            }),
+        }
+    }
+}
+
+impl<'a, R: 'a, W: 'a + Monoid, S: 'a, A: 'a> Apply<'a> for RWS<'a, R, W, S, A> {
+    fn ap<F, B: 'a>(self, f: Self::Wrapped<F>) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> B + 'a,
+    {
+        RWS {
+            runner: Box::new(move |r, s| {
+                let (a1, s1, w1) = (self.runner)(r, s);
+                let (f, s2, w2) = (f.runner)(r, s1);
+                (f(a1), s2, w1.mappend(w2))
+            }),
+        }
+    }
+
+    fn lift_a2<F, B: 'a, C: 'a>(self, b: Self::Wrapped<B>, f: F) -> Self::Wrapped<C>
+    where
+        F: FnOnce(Self::Unwrapped, B) -> C + 'a,
+    {
+        RWS {
+            runner: Box::new(move |r, s| {
+                let (a1, s1, w1) = (self.runner)(r, s);
+                let (a2, s2, w2) = (b.runner)(r, s1);
+                (f(a1, a2), s2, w1.mappend(w2))
+            }),
+        }
+    }
+}
+
+impl<'a, R: 'a, W: 'a + Monoid, S: 'a, A: 'a> Applicative<'a> for RWS<'a, R, W, S, A> {
+    fn of(value: Self::Unwrapped) -> Self::Wrapped<Self::Unwrapped> {
+        RWS::new(|_, s| (value, s, W::mempty()))
+    }
+}
+
+impl<'a, R: 'a, W: 'a + Monoid, S: 'a, A: 'a> Monad<'a> for RWS<'a, R, W, S, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = RWS<'a, R, W, S, B>;
+
+    fn bind<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> Self::Wrapped<B> + 'a,
+    {
+        RWS {
+            runner: Box::new(move |r, s| {
+                let (a1, s1, w1) = (self.runner)(r, s);
+                let (a2, s2, w2) = (f(a1).runner)(r, s1);
+                (a2, s2, w1.mappend(w2))
+            }),
+        }
+    }
+
+    fn of<T: 'a>(value: T) -> Self::Wrapped<T> {
+        RWS::new(|_, s| (value, s, W::mempty()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::m;
+    use crate::Apply;
+    use crate::Functor;
+    use crate::Monad;
+    use crate::RWS;
+
+    #[test]
+    fn rws_fmap() {
+        let rws = RWS::new(|r: &i32, s: i32| (r + s, s, "FOO".to_string()));
+
+        let rws2 = rws.fmap(|a| a * 2);
+        assert_eq!(rws2.run(&1, 20), (42, 20, "FOO".to_string()));
+    }
+
+    #[test]
+    fn rws_lifta2() {
+        let a = RWS::new(|r: &i32, s: i32| (*r, s + 1, vec!["a"]));
+        let b = RWS::new(|r: &i32, s: i32| (r * s, s, vec!["b"]));
+
+        let res = a.lift_a2(b, |x, y| x + y);
+
+        assert_eq!(res.run(&2, 20), (44, 21, vec!["a", "b"]));
+    }
+
+    #[test]
+    fn rws_bind() {
+        let rws = RWS::new(|r: &i32, s: String| (*r, s, "FOO".to_string()));
+
+        let rws2 = rws.bind(|a| RWS::new(move |_, s| (a + 41, format!("{}_BAR", s), "BAR".into())));
+
+        assert_eq!(
+            rws2.run(&1, "S".into()),
+            (42, "S_BAR".to_string(), "FOOBAR".to_string())
+        );
+    }
+
+    #[test]
+    fn rws_macro() {
+        let step = || {
+            m! {
+                incr <- RWS::asks(|r: &i32| *r);
+                count <- RWS::get();
+                RWS::tell(vec![format!("count: {}", count)]);
+                RWS::put(count + incr);
+                RWS::modify(|s| s * 2)
+            }
+        };
+
+        let prog = m! {
+            step();
+            step();
+            RWS::ask().local(|r| r * 100)
+        };
+
+        assert_eq!(
+            prog.run(&1, 0),
+            (100, 6, vec!["count: 0".to_string(), "count: 2".to_string()])
+        );
+        assert_eq!(step().eval(&1, 0), ((), vec!["count: 0".to_string()]));
+        assert_eq!(step().exec(&1, 0), (2, vec!["count: 0".to_string()]));
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{Applicative, Apply, Functor, Monad};
+use std::ops::ControlFlow;
+
+use crate::{
+    Applicative, Apply, Functor, Identity, Lens, Monad, MonadRec, MonadState, MonadWrapped, StateT,
+};
 
 /// `State` describes a wrapped function that can be used to pass around some
 /// "hidden" pure state.
@@ -6,12 +10,19 @@ use crate::{Applicative, Apply, Functor, Monad};
 /// gets passed around with the value, and the monadic DSL helps it feel more
 /// natural than passing everything around by hand.
 /// Simulates a global mutable state by means of composition of pure functions.
-
+///
+/// `State<S, A>` is a `StateT<S, Identity<(A, S)>>` under the hood: every operation goes through
+/// the implementation of `StateT`, and the `From` conversions between them just move the value.
+///
+/// `execute` runs the binds one after the other in the loop of `Eval`, instead of calling their runners
+/// inside each other: chains of any length (e.g. a simulation loop written with `m!`) don't overflow the stack.
+///
 /// This module is inspired by the paper Functional Programming with Overloading and Higher-Order Polymorphism,
 /// Mark P Jones <http://web.cecs.pdx.edu/~mpj/> Advanced School of Functional Programming, 1995.
+#[repr(transparent)]
 pub struct State<'a, S, A> {
-    /// The (apparently) "stateful" function
-    pub runner: Box<dyn 'a + FnOnce(S) -> (A, S)>,
+    /// The `StateT` over `Identity` which runs the (apparently) "stateful" function
+    pub(crate) runner: StateT<'a, S, Identity<(A, S)>>,
 }
 
 impl<'a, S: 'a, A: 'a> State<'a, S, A> {
@@ -21,14 +32,27 @@ impl<'a, S: 'a, A: 'a> State<'a, S, A> {
         F: FnOnce(S) -> (A, S) + 'a,
     {
         Self {
-            runner: Box::new(runner),
+            runner: StateT::new(move |s| Identity::new(runner(s))),
         }
     }
 
     /// Run a `State` by passing in some initial state to actualy run the enclosed
     /// state runner.
     pub fn execute(self, state: S) -> (A, S) {
-        (self.runner)(state)
+        self.runner.trampoline(state).run().execute()
+    }
+
+    /// Run this `State` on a part of a bigger state T, focused by the lens:
+    /// the rest of the bigger state is left untouched.
+    ///
+    /// Haskell signature
+    /// zoom :: Lens' t s -> State s a -> State t a
+    pub fn zoom<T: 'a>(self, lens: Lens<'a, T, S>) -> State<'a, T, A> {
+        State::new(move |t| {
+            let s = lens.view(&t);
+            let (a, s1) = self.execute(s);
+            (a, lens.set(t, s1))
+        })
     }
 }
 
@@ -41,12 +65,7 @@ impl<'a, S: 'a, A: 'a> Functor<'a> for State<'a, S, A> {
     where
         F: FnOnce(Self::Unwrapped) -> B + 'a,
     {
-        State {
-            runner: Box::new(move |s| {
-                let (a1, s1) = (self.runner)(s);
-                (f(a1), s1)
-            }),
-        }
+        self.bind(move |a1| State::new(move |s| (f(a1), s)))
     }
 }
 
@@ -55,26 +74,14 @@ impl<'a, S: 'a, A: 'a> Apply<'a> for State<'a, S, A> {
     where
         F: FnOnce(Self::Unwrapped) -> B + 'a,
     {
-        State {
-            runner: Box::new(move |s| {
-                let (a1, s1) = (self.runner)(s);
-                let (f, s2) = (f.runner)(s1);
-                (f(a1), s2)
-            }),
-        }
+        self.bind(move |a1| f.bind(move |f| State::new(move |s| (f(a1), s))))
     }
 
     fn lift_a2<F, B: 'a, C: 'a>(self, b: Self::Wrapped<B>, f: F) -> Self::Wrapped<C>
     where
         F: FnOnce(Self::Unwrapped, B) -> C + 'a,
     {
-        State {
-            runner: Box::new(move |s| {
-                let (a1, s1) = (self.runner)(s);
-                let (a2, s2) = (b.runner)(s1);
-                (f(a1, a2), s2)
-            }),
-        }
+        self.bind(move |a1| b.bind(move |a2| State::new(move |s| (f(a1, a2), s))))
     }
 }
 
@@ -94,44 +101,77 @@ impl<'a, S: 'a, A: 'a> Monad<'a> for State<'a, S, A> {
         F: FnOnce(Self::Unwrapped) -> Self::Wrapped<B> + 'a,
     {
         State {
-            runner: Box::new(move |s| {
-                let (a1, s1) = (self.runner)(s);
-                let g = f(a1).runner;
-                (g)(s1)
-            }),
+            runner: self.runner.bind(move |a1| f(a1).runner),
         }
     }
 
     fn of<T: 'a>(value: T) -> Self::Wrapped<T> {
-        State::new(|s| (value, s))
+        State {
+            runner: StateT::<S, Identity<(A, S)>>::of(value),
+        }
+    }
+}
+
+impl<'a, S: 'a, A: 'a> MonadWrapped<'a> for State<'a, S, A> {
+    fn bind_wrapped<X: 'a, Y: 'a, F>(mx: State<'a, S, X>, f: F) -> State<'a, S, Y>
+    where
+        F: FnOnce(X) -> State<'a, S, Y> + 'a,
+    {
+        mx.bind(f)
+    }
+}
+
+impl<'a, S: 'a, A: 'a> MonadRec<'a> for State<'a, S, A> {
+    fn tail_rec_m<B: 'a, C: 'a, F>(init: B, mut f: F) -> Self::Wrapped<C>
+    where
+        F: FnMut(B) -> Self::Wrapped<ControlFlow<C, B>> + 'a,
+    {
+        State::new(move |s| {
+            let (mut step, mut state) = f(init).execute(s);
+            loop {
+                match step {
+                    ControlFlow::Continue(b) => (step, state) = f(b).execute(state),
+                    ControlFlow::Break(c) => return (c, state),
+                }
+            }
+        })
+    }
+}
+
+impl<'a, S: 'a, A: 'a> MonadState<'a, S> for State<'a, S, A> {
+    fn state<F>(f: F) -> Self
+    where
+        F: FnOnce(S) -> (A, S) + 'a,
+    {
+        State::new(f)
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::ops::ControlFlow;
+
+    use crate::m;
     use crate::Apply;
     use crate::Functor;
+    use crate::Lens;
     use crate::Monad;
+    use crate::MonadRec;
+    use crate::MonadState;
     use crate::State;
 
     #[test]
     fn state_fmap() {
-        let s = State {
-            runner: Box::new(|s| (12, s)),
-        };
+        let s = State::new(|s| (12, s));
 
         let s2 = s.fmap(|a| a + 2);
-        assert_eq!((s2.runner)("DOES_NOT_MATTER"), (14, "DOES_NOT_MATTER"));
+        assert_eq!(s2.execute("DOES_NOT_MATTER"), (14, "DOES_NOT_MATTER"));
     }
 
     #[test]
     fn state_ap() {
-        let s = State {
-            runner: Box::new(|s| (12, s)),
-        };
-        let b = State {
-            runner: Box::new(|s| (|x| format!("{}", x), s)),
-        };
+        let s = State::new(|s| (12, s));
+        let b = State::new(|s| (|x| format!("{}", x), s));
 
         let res = s.ap(b);
 
@@ -140,12 +180,8 @@ mod test {
 
     #[test]
     fn state_lifta2() {
-        let s = State {
-            runner: Box::new(|s| (12, s)),
-        };
-        let b = State {
-            runner: Box::new(|s| (14, format!("{}BAR", s))),
-        };
+        let s = State::new(|s| (12, s));
+        let b = State::new(|s| (14, format!("{}BAR", s)));
 
         let res = s.lift_a2(b, |x, y| x + y);
 
@@ -154,14 +190,84 @@ mod test {
 
     #[test]
     fn state_bind() {
-        let s: State<String, i32> = State {
-            runner: Box::new(|s| (12, s)),
-        };
+        let s: State<String, i32> = State::new(|s| (12, s));
+
+        let s2 = s.bind(|a| State::new(move |s| (a + 30, format!("{}_BAR", s))));
+
+        assert_eq!(s2.execute("FOO".into()), (42, "FOO_BAR".into()));
+    }
+
+    #[test]
+    fn state_million_left_binds() {
+        let mut s = State::new(|s: u64| (0u64, s));
+        for _ in 0..1_000_000 {
+            s = s.bind(|a| State::new(move |s| (a + 1, s + 2)));
+        }
+
+        assert_eq!(s.execute(0), (1_000_000, 2_000_000));
+    }
+
+    #[test]
+    fn state_million_steps_m() {
+        fn simulate<'a>(steps: u32) -> State<'a, (u64, u64), u32> {
+            if steps == 0 {
+                State::new(|s| (0, s))
+            } else {
+                m! {
+                    _ <- State::new(|(a, b): (u64, u64)| ((), (b, (a + b) % 1_000_007)));
+                    n <- simulate(steps - 1);
+                    State::new(move |s| (n + 1, s))
+                }
+            }
+        }
+
+        let (steps, (a, _)) = simulate(1_000_000).execute((0, 1));
 
-        let s2 = s.bind(|a| State {
-            runner: Box::new(move |s| (a + 30, format!("{}_BAR", s))),
+        assert_eq!(steps, 1_000_000);
+        assert!(a < 1_000_007);
+    }
+
+    #[test]
+    fn state_simulation_loop() {
+        let simulate = State::<(u64, u64), ()>::tail_rec_m(100_000, |steps: u32| {
+            State::new(move |(a, b): (u64, u64)| {
+                if steps == 0 {
+                    (ControlFlow::Break(steps), (a, b))
+                } else {
+                    (ControlFlow::Continue(steps - 1), (b, (a + b) % 1_000_007))
+                }
+            })
         });
 
-        assert_eq!((s2.runner)("FOO".into()), (42, "FOO_BAR".into()));
+        let (steps, (a, _)) = simulate.execute((0, 1));
+
+        assert_eq!(steps, 0);
+        assert!(a < 1_000_007);
+    }
+
+    #[test]
+    fn state_zoom() {
+        let counter = State::new(|n: i32| (n, n + 1));
+        let first = Lens::new(|p: &(i32, String)| p.0, |p, n| (n, p.1));
+
+        let zoomed = m! {
+            a <- counter.zoom(first.clone());
+            b <- State::new(|n: i32| (n, n + 1)).zoom(first);
+            State::new(move |(n, s): (i32, String)| (a + b, (n, format!("{}!", s))))
+        };
+
+        assert_eq!(zoomed.execute((20, "hi".into())), (41, (22, "hi!".into())));
+    }
+
+    #[test]
+    fn state_get_put() {
+        let counter: State<i32, String> = m! {
+            n <- State::get();
+            State::put(n + 1);
+            State::modify(|n| n * 2);
+            State::gets(|n: &i32| format!("was {}", n))
+        };
+
+        assert_eq!(counter.execute(20), ("was 42".to_string(), 42));
     }
 }
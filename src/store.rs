@@ -0,0 +1,208 @@
+use std::rc::Rc;
+
+use crate::{Comonad, Functor};
+
+/// `Store` is a lookup function paired with a current position: a value of type A at every position S,
+/// focused on one of them.
+///
+/// It is the comonad of spatial computations: `extend` runs a function on the store focused on each position,
+/// and the function can `peek` at the neighbourhood of the focus, as in cellular automata or image filters.
+/// `extend` is lazy, so successive generations recompute their ancestors on demand:
+/// long-running computations usually tabulate the results of each step into a new lookup function.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/comonad/docs/Control-Comonad-Store.html>
+///
+/// ```haskell
+/// data Store s a = Store (s -> a) s
+/// ```
+pub struct Store<'a, S, A> {
+    /// The value at every position
+    lookup: Rc<dyn 'a + Fn(S) -> A>,
+    /// The position under focus
+    pos: S,
+}
+
+impl<'a, S: 'a, A: 'a> Store<'a, S, A> {
+    /// Constructs a new `Store` from its lookup function, focused on `pos`.
+    pub fn new<F>(lookup: F, pos: S) -> Self
+    where
+        F: Fn(S) -> A + 'a,
+    {
+        Store {
+            lookup: Rc::new(lookup),
+            pos,
+        }
+    }
+
+    /// The position under focus.
+    pub fn pos(&self) -> &S {
+        &self.pos
+    }
+
+    /// Read the value at any position.
+    pub fn peek(&self, pos: S) -> A {
+        (self.lookup)(pos)
+    }
+
+    /// Move the focus to `pos`.
+    pub fn seek(self, pos: S) -> Self {
+        Store {
+            lookup: self.lookup,
+            pos,
+        }
+    }
+
+    /// Move the focus relatively to the current position.
+    pub fn seeks<F>(self, f: F) -> Self
+    where
+        F: FnOnce(S) -> S,
+    {
+        Store {
+            lookup: self.lookup,
+            pos: f(self.pos),
+        }
+    }
+}
+
+impl<'a, S: Clone, A> Clone for Store<'a, S, A> {
+    fn clone(&self) -> Self {
+        Store {
+            lookup: self.lookup.clone(),
+            pos: self.pos.clone(),
+        }
+    }
+}
+
+impl<'a, S: 'a, A: 'a> Functor<'a> for Store<'a, S, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Store<'a, S, B>;
+
+    fn fmap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(Self::Unwrapped) -> B + 'a,
+    {
+        let lookup = self.lookup;
+        Store::new(move |s| f(lookup(s)), self.pos)
+    }
+}
+
+impl<'a, S: 'a, A: 'a> Comonad<'a> for Store<'a, S, A> {
+    fn extract(self) -> Self::Unwrapped {
+        (self.lookup)(self.pos)
+    }
+
+    fn extend<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(Self) -> B + 'a,
+    {
+        // extend f (Store g s) = Store (f . Store g) s
+        let lookup = self.lookup;
+        Store::new(
+            move |s| {
+                f(Store {
+                    lookup: lookup.clone(),
+                    pos: s,
+                })
+            },
+            self.pos,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Comonad;
+    use crate::Functor;
+    use crate::Store;
+
+    #[test]
+    fn store_peek_seek() {
+        let squares = Store::new(|i: i32| i * i, 3);
+        assert_eq!(*squares.pos(), 3);
+        assert_eq!(squares.peek(5), 25);
+        assert_eq!(squares.clone().extract(), 9);
+        assert_eq!(squares.clone().seek(4).extract(), 16);
+        assert_eq!(squares.seeks(|i| i - 1).fmap(|a| a + 1).extract(), 5);
+    }
+
+    #[test]
+    fn store_extend_is_local() {
+        // Discrete derivative: each position looks at its right neighbour
+        let squares = Store::new(|i: i32| i * i, 0);
+        let derivative = squares.extend(|w| w.peek(w.pos() + 1) - w.clone().extract());
+
+        assert_eq!(
+            (0..5).map(|i| derivative.peek(i)).collect::<Vec<_>>(),
+            vec![1, 3, 5, 7, 9]
+        );
+    }
+
+    const SIZE: i32 = 6;
+
+    type Board<'a> = Store<'a, (i32, i32), bool>;
+
+    fn board<'a>(rows: &[&str]) -> Board<'a> {
+        let cells: Vec<Vec<bool>> = rows
+            .iter()
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect();
+        Store::new(
+            move |(x, y)| {
+                (0..SIZE).contains(&x) && (0..SIZE).contains(&y) && cells[y as usize][x as usize]
+            },
+            (0, 0),
+        )
+    }
+
+    fn render(board: &Board) -> Vec<String> {
+        (0..SIZE)
+            .map(|y| {
+                (0..SIZE)
+                    .map(|x| if board.peek((x, y)) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// One generation of the Game of Life, computed cell by cell from its neighbourhood
+    fn life(cell: Board) -> bool {
+        let (x, y) = *cell.pos();
+        let neighbours = [-1, 0, 1]
+            .iter()
+            .flat_map(|dx| [-1, 0, 1].iter().map(move |dy| (*dx, *dy)))
+            .filter(|&(dx, dy)| (dx, dy) != (0, 0) && cell.peek((x + dx, y + dy)))
+            .count();
+
+        matches!((cell.extract(), neighbours), (true, 2) | (_, 3))
+    }
+
+    fn step<'a>(board: Board<'a>) -> Board<'a> {
+        // Tabulate the new generation, so the next one doesn't recompute it
+        let rows = render(&board.extend(life));
+        let rows: Vec<&str> = rows.iter().map(|row| row.as_str()).collect();
+        self::board(&rows)
+    }
+
+    #[test]
+    fn store_game_of_life() {
+        let blinker = board(&["......", "..#...", "..#...", "..#...", "......", "......"]);
+
+        let next = step(blinker);
+        assert_eq!(
+            render(&next),
+            vec!["......", "......", ".###..", "......", "......", "......"]
+        );
+        assert_eq!(
+            render(&step(next)),
+            vec!["......", "..#...", "..#...", "..#...", "......", "......"]
+        );
+
+        let glider = board(&[".#....", "..#...", "###...", "......", "......", "......"]);
+        let moved = (0..4).fold(glider, |board, _| step(board));
+        assert_eq!(
+            render(&moved),
+            vec!["......", "..#...", "...#..", ".###..", "......", "......"]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::rc::Rc;
+
+use crate::{Comonad, Functor, Monoid};
+
+/// `Traced` (a.k.a. the co-writer) is a function from a `Monoid` of "positions relative to here"
+/// to values: `extract` reads the value at `mempty`, the current position.
+///
+/// It is the comonadic dual of `Writer`: `extend` gives every position access to the values
+/// reachable by appending more to its own trace, e.g. to compute a value from its future.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/comonad/docs/Control-Comonad-Traced.html>
+///
+/// ```haskell
+/// newtype Traced m a = Traced { runTraced :: m -> a }
+/// ```
+pub struct Traced<'a, M, A> {
+    runner: Rc<dyn 'a + Fn(M) -> A>,
+}
+
+impl<'a, M: 'a, A: 'a> Traced<'a, M, A> {
+    /// Constructs a new `Traced` from the value at every relative position.
+    pub fn new<F>(runner: F) -> Self
+    where
+        F: Fn(M) -> A + 'a,
+    {
+        Traced {
+            runner: Rc::new(runner),
+        }
+    }
+
+    /// Read the value at the position `m` relative to the current one.
+    pub fn trace(&self, m: M) -> A {
+        (self.runner)(m)
+    }
+}
+
+impl<'a, M, A> Clone for Traced<'a, M, A> {
+    fn clone(&self) -> Self {
+        Traced {
+            runner: self.runner.clone(),
+        }
+    }
+}
+
+impl<'a, M: 'a, A: 'a> Functor<'a> for Traced<'a, M, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Traced<'a, M, B>;
+
+    fn fmap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(Self::Unwrapped) -> B + 'a,
+    {
+        let runner = self.runner;
+        Traced::new(move |m| f(runner(m)))
+    }
+}
+
+impl<'a, M: Monoid + Clone + 'a, A: 'a> Comonad<'a> for Traced<'a, M, A> {
+    fn extract(self) -> Self::Unwrapped {
+        (self.runner)(M::mempty())
+    }
+
+    fn extend<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(Self) -> B + 'a,
+    {
+        // extend f (Traced g) = Traced (\m -> f (Traced (g . mappend m)))
+        let runner = self.runner;
+        Traced::new(move |m: M| {
+            let runner = runner.clone();
+            f(Traced::new(move |m2| runner(m.clone().mappend(m2))))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Comonad;
+    use crate::Functor;
+    use crate::Traced;
+
+    #[test]
+    fn traced_trace_extract() {
+        // A value at every offset from the origin
+        let line = Traced::new(|offset: i32| offset * 2 + 1);
+        assert_eq!(line.trace(3), 7);
+        assert_eq!(line.clone().extract(), 1);
+        assert_eq!(line.fmap(|v| v * 10).extract(), 10);
+    }
+
+    #[test]
+    fn traced_extend() {
+        let line = Traced::new(|offset: i32| offset * 2 + 1);
+
+        // Each position sees the value 2 steps further
+        let ahead = line.extend(|w| w.trace(2));
+        assert_eq!(ahead.clone().extract(), 5);
+        assert_eq!(ahead.trace(1), 7);
+
+        // Paths are built by appending to the trace
+        let path = Traced::new(|steps: Vec<&str>| steps.join("/"));
+        let under_usr = path.extend(|w| w.trace(vec!["usr"]));
+        assert_eq!(under_usr.trace(vec!["bin"]), "bin/usr");
+        assert_eq!(under_usr.extract(), "usr");
+    }
+}
@@ -0,0 +1,222 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{Applicative, Apply, Functor, Monad, MonadTrans};
+
+/// A continuation: the rest of the computation, waiting for a value of type A to produce the final result R
+pub type Continuation<'a, A, R> = Box<dyn 'a + FnOnce(A) -> R>;
+
+/// A continuation transformer monad parameterized by the inner monad (M), holding the final result,
+/// and the value passed to the continuation (A).
+///
+/// Since `bind` only accepts `FnOnce` functions, continuations are one-shot: each of them can be resumed at most once.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/transformers/docs/Control-Monad-Trans-Cont.html>
+///
+/// ```haskell
+/// newtype ContT r m a = ContT { runContT :: (a -> m r) -> m r }
+/// ```
+pub struct ContT<'a, M, A> {
+    /// The function which takes the continuation and return the final result
+    pub runner: Box<dyn 'a + FnOnce(Continuation<'a, A, M>) -> M>,
+}
+
+/// The escape continuation captured by `call_cc`.
+///
+/// Calling `exit` discards the rest of the computation and resumes from the point where `call_cc` was invoked.
+/// Like every continuation of `ContT` it is one-shot, so `Exit` can't be cloned and `exit` consumes it.
+pub struct Exit<'a, M, A> {
+    k: Rc<RefCell<Option<Continuation<'a, A, M>>>>,
+}
+
+impl<'a, M: 'a, A: 'a> Exit<'a, M, A> {
+    /// Escape with `value`, skipping the rest of the current computation.
+    pub fn exit<B: 'a>(self, value: A) -> ContT<'a, M, B> {
+        ContT::new(move |_| self.resume(value))
+    }
+
+    fn resume(self, value: A) -> M {
+        let k = self
+            .k
+            .borrow_mut()
+            .take()
+            .expect("a continuation can be resumed only once");
+        k(value)
+    }
+}
+
+impl<'a, M: 'a, A: 'a> ContT<'a, M, A> {
+    pub fn new<F>(runner: F) -> Self
+    where
+        F: FnOnce(Continuation<'a, A, M>) -> M + 'a,
+    {
+        Self {
+            runner: Box::new(runner),
+        }
+    }
+
+    /// Run the computation by passing in the final continuation.
+    pub fn run_cont<K>(self, k: K) -> M
+    where
+        K: FnOnce(A) -> M + 'a,
+    {
+        (self.runner)(Box::new(k))
+    }
+
+    /// Call with current continuation: `f` receives an `Exit` which can be used
+    /// to return early from the computation built by `f`.
+    pub fn call_cc<F>(f: F) -> Self
+    where
+        F: FnOnce(Exit<'a, M, A>) -> Self + 'a,
+    {
+        ContT::new(move |k| {
+            let k = Rc::new(RefCell::new(Some(k)));
+            let exit = Exit { k: k.clone() };
+
+            // Either `exit` escapes or the computation built by `f` returns normally: `k` runs once.
+            f(exit).run_cont(move |a| Exit { k }.resume(a))
+        })
+    }
+
+    /// Run the computation with `of` as the final continuation.
+    pub fn eval_cont_t(self) -> M
+    where
+        M: Monad<'a, Unwrapped = A, Wrapped<A> = M>,
+    {
+        self.run_cont(|a| M::of(a))
+    }
+
+    /// Delimit the continuations captured by `shift_t` inside `inner`.
+    pub fn reset_t(inner: Self) -> Self
+    where
+        M: Monad<'a, Unwrapped = A, Wrapped<A> = M>,
+    {
+        ContT::new(move |k| inner.eval_cont_t().bind::<_, A>(k))
+    }
+
+    /// Capture the continuation up to the nearest enclosing `reset_t` and pass it to `f`.
+    pub fn shift_t<R: 'a, F>(f: F) -> Self
+    where
+        M: Monad<'a, Unwrapped = R, Wrapped<R> = M>,
+        F: FnOnce(Continuation<'a, A, M>) -> ContT<'a, M, R> + 'a,
+    {
+        ContT::new(move |k| f(k).eval_cont_t())
+    }
+}
+
+impl<'a, M: 'a, A: 'a> Functor<'a> for ContT<'a, M, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = ContT<'a, M, B>;
+
+    fn fmap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> B + 'a,
+    {
+        ContT::new(move |k| self.run_cont(move |a| k(f(a))))
+    }
+}
+
+impl<'a, M: 'a, A: 'a> Apply<'a> for ContT<'a, M, A> {
+    fn ap<F, B: 'a>(self, f: Self::Wrapped<F>) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> B + 'a,
+    {
+        ContT::new(move |k| f.run_cont(move |g| self.run_cont(move |a| k(g(a)))))
+    }
+
+    fn lift_a2<F, B: 'a, C: 'a>(self, b: Self::Wrapped<B>, f: F) -> Self::Wrapped<C>
+    where
+        F: FnOnce(Self::Unwrapped, B) -> C + 'a,
+    {
+        ContT::new(move |k| self.run_cont(move |a1| b.run_cont(move |a2| k(f(a1, a2)))))
+    }
+}
+
+impl<'a, M: 'a, A: 'a> Applicative<'a> for ContT<'a, M, A> {
+    fn of(value: Self::Unwrapped) -> Self::Wrapped<Self::Unwrapped> {
+        ContT::new(move |k| k(value))
+    }
+}
+
+impl<'a, M: 'a, A: 'a> Monad<'a> for ContT<'a, M, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = ContT<'a, M, B>;
+
+    fn bind<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> Self::Wrapped<B> + 'a,
+    {
+        ContT::new(move |k| self.run_cont(move |a| (f(a).runner)(k)))
+    }
+
+    fn of<T: 'a>(value: T) -> Self::Wrapped<T> {
+        ContT::new(move |k| k(value))
+    }
+}
+
+impl<'a, M: 'a + Monad<'a>, A: 'a> MonadTrans<'a> for ContT<'a, M, A>
+where
+    M::Wrapped<A>: 'a + Monad<'a, Unwrapped = A, Wrapped<M::Unwrapped> = M>,
+    M::Unwrapped: 'a,
+{
+    type Inner = M::Wrapped<A>;
+
+    fn lift(base: Self::Inner) -> Self {
+        ContT::new(move |k| base.bind::<_, M::Unwrapped>(k))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::m;
+    use crate::ContT;
+    use crate::Continuation;
+    use crate::Monad;
+    use crate::MonadTrans;
+
+    #[test]
+    fn cont_t_bind() {
+        let c_t: ContT<Option<i32>, i32> = MonadTrans::lift(Option::Some(1));
+
+        let c_t2 = c_t.bind(|a| MonadTrans::lift(Option::Some(a + 41)));
+
+        assert_eq!(c_t2.eval_cont_t(), Option::Some(42));
+    }
+
+    #[test]
+    fn cont_t_bind_fail() {
+        let c_t: ContT<Option<i32>, i32> = MonadTrans::lift(Option::Some(1));
+
+        let c_t2 = c_t.bind(|_a| MonadTrans::lift(Option::<i32>::None));
+
+        assert_eq!(c_t2.eval_cont_t(), Option::None);
+    }
+
+    #[test]
+    fn cont_t_call_cc() {
+        let lookup = |key: i32| {
+            ContT::call_cc(move |exit| {
+                let doubled: ContT<Option<i32>, i32> = MonadTrans::lift(Option::Some(key * 2));
+                m! {
+                    v <- doubled;
+                    _ <- if v > 10 { exit.exit(-1) } else { MonadTrans::lift(Option::Some(())) };
+                    MonadTrans::lift(Option::Some(v + 1))
+                }
+            })
+        };
+
+        assert_eq!(lookup(4).eval_cont_t(), Option::Some(9));
+        assert_eq!(lookup(40).eval_cont_t(), Option::Some(-1));
+    }
+
+    #[test]
+    fn cont_t_shift_reset() {
+        let c_t = ContT::reset_t(m! {
+            x <- ContT::shift_t(|k: Continuation<i32, Option<i32>>| MonadTrans::lift(k(10).map(|r| r * 2)));
+            MonadTrans::lift(Option::Some(x + 1))
+        });
+
+        assert_eq!(c_t.eval_cont_t(), Option::Some(22));
+    }
+}
@@ -1,7 +1,27 @@
 /// A monad transformer is a type constructor which takes a monad as an argument and returns a monad as a result.
 /// Monad transformers can be used to compose and stack monads – such as state and exception handling – in a modular way.
+mod monad_trans;
+pub use crate::trans::monad_trans::MonadTrans;
+
 mod state_t;
 pub use crate::trans::state_t::StateT;
 
 mod result_t;
 pub use crate::trans::result_t::ResultT;
+
+mod reader_t;
+pub use crate::trans::reader_t::ReaderT;
+
+mod writer_t;
+pub use crate::trans::writer_t::WriterT;
+
+mod option_t;
+pub use crate::trans::option_t::OptionT;
+
+mod rws_t;
+pub use crate::trans::rws_t::RWST;
+
+mod cont_t;
+pub use crate::trans::cont_t::ContT;
+pub use crate::trans::cont_t::Continuation;
+pub use crate::trans::cont_t::Exit;
@@ -0,0 +1,27 @@
+use crate::Monad;
+
+/// `MonadTrans` lifts a computation of the inner monad into a transformer, which runs it without
+/// adding any effect of its own.
+///
+/// `Inner` is the inner monad yielding the same values as the transformer, so that the type of the
+/// lifted computation follows from the type of the transformer: a chain of `lift`s climbing several layers
+/// of a stack is inferred from the type of the whole stack, e.g.
+/// `let log: StateT<S, ResultT<Writer<Result<((), S), E>, W>>> = MonadTrans::lift(MonadTrans::lift(Writer::tell(w)));`
+///
+/// A lawful instance satisfies:
+/// - `lift(M::of(a)) == Self::of(a)`
+/// - `lift(m.bind(f)) == lift(m).bind(|a| lift(f(a)))`
+///
+/// Haskell definition from <https://hackage.haskell.org/package/transformers/docs/Control-Monad-Trans-Class.html>
+///
+/// ```haskell
+/// class (forall m. Monad m => Monad (t m)) => MonadTrans t where
+///   lift :: Monad m => m a -> t m a
+/// ```
+pub trait MonadTrans<'a>: Monad<'a> {
+    /// The inner monad, yielding values of type `Self::Unwrapped`
+    type Inner;
+
+    /// Lift a computation of the inner monad into the transformer.
+    fn lift(base: Self::Inner) -> Self;
+}
@@ -0,0 +1,251 @@
+use crate::{Alternative, Monad, MonadPlus, MonadTrans, ResultT};
+
+/// An `Option` (`Maybe`) transformer monad parameterized by the inner monad (M)
+pub struct OptionT<M> {
+    /// The inner monad whose Unwrapped value is Option<_>
+    pub runner: M,
+}
+
+impl<'a, M> OptionT<M> {
+    pub fn new(runner: M) -> Self {
+        Self { runner }
+    }
+
+    pub fn execute(self) -> M {
+        self.runner
+    }
+
+    /// A computation which yields nothing.
+    pub fn none<A: 'a>() -> Self
+    where
+        M: Monad<'a, Unwrapped = Option<A>, Wrapped<Option<A>> = M>,
+    {
+        OptionT::new(M::of(Option::None))
+    }
+
+    /// Run `f` if (and only if) this computation yields nothing.
+    pub fn or_else<A: 'a, F>(self, f: F) -> Self
+    where
+        M: Monad<'a, Unwrapped = Option<A>, Wrapped<Option<A>> = M>,
+        F: FnOnce() -> Self + 'a,
+    {
+        OptionT::new(self.runner.bind::<_, Option<A>>(|o| match o {
+            Some(a) => M::of(Option::Some(a)),
+            None => f().runner,
+        }))
+    }
+
+    /// Convert into a `ResultT`, replacing "nothing" with the given `err`.
+    pub fn into_result_t<A: 'a, E: 'a>(
+        self,
+        err: E,
+    ) -> ResultT<<M as Monad<'a>>::Wrapped<Result<A, E>>>
+    where
+        M: Monad<'a, Unwrapped = Option<A>>,
+    {
+        ResultT::new(self.runner.bind(|o| M::of(o.ok_or(err))))
+    }
+
+    /// Convert from a `ResultT`, discarding the error.
+    pub fn from_result_t<N, A: 'a, E: 'a>(r: ResultT<N>) -> Self
+    where
+        N: Monad<'a, Unwrapped = Result<A, E>, Wrapped<Option<A>> = M>,
+    {
+        OptionT::new(r.runner.bind::<_, Option<A>>(|r| N::of(r.ok())))
+    }
+}
+
+impl<'a, M: 'a + Monad<'a, Unwrapped = Option<A>>, A: 'a> Monad<'a> for OptionT<M> {
+    type Unwrapped = A;
+
+    type Wrapped<C: 'a> = OptionT<M::Wrapped<Option<C>>>;
+
+    fn bind<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> Self::Wrapped<B> + 'a,
+    {
+        OptionT::new(self.runner.bind(|o| match o {
+            Some(a) => f(a).runner,
+            None => M::of(Option::None),
+        }))
+    }
+
+    fn of<T: 'a>(value: T) -> Self::Wrapped<T> {
+        OptionT::new(M::of(Option::Some(value)))
+    }
+}
+
+impl<'a, M, A: 'a> MonadTrans<'a> for OptionT<M>
+where
+    M: 'a + Monad<'a, Unwrapped = Option<A>>,
+    M::Wrapped<A>: Monad<'a, Unwrapped = A, Wrapped<Option<A>> = M>,
+{
+    type Inner = M::Wrapped<A>;
+
+    fn lift(base: Self::Inner) -> Self {
+        OptionT::new(base.bind::<_, Option<A>>(|a| M::Wrapped::<A>::of(Option::Some(a))))
+    }
+}
+
+impl<'a, M, A: 'a> Alternative<'a> for OptionT<M>
+where
+    M: 'a + Monad<'a, Unwrapped = Option<A>, Wrapped<Option<A>> = M>,
+{
+    fn empty() -> Self {
+        OptionT::none()
+    }
+
+    /// The other computation only runs if this one yields nothing.
+    fn alt(self, other: Self) -> Self {
+        self.or_else(|| other)
+    }
+}
+
+impl<'a, M, A: 'a> MonadPlus<'a> for OptionT<M> where
+    M: 'a + Monad<'a, Unwrapped = Option<A>, Wrapped<Option<A>> = M>
+{
+}
+
+#[cfg(test)]
+mod test {
+    use crate::m;
+    use crate::Alternative;
+    use crate::Functor;
+    use crate::Monad;
+    use crate::MonadTrans;
+    use crate::OptionT;
+    use crate::ResultT;
+    use crate::State;
+
+    #[test]
+    fn option_t_bind() {
+        let o_t = OptionT::new(Result::<_, String>::Ok(Option::Some(1)));
+
+        let o_t2 = o_t.bind(move |a| OptionT::new(Result::Ok(Option::Some(a + 41))));
+
+        assert_eq!(o_t2.runner, Result::Ok(Option::Some(42)));
+    }
+
+    #[test]
+    fn option_t_none() {
+        let o_t = OptionT::<Result<Option<i32>, String>>::none();
+        let o_t2 = o_t.bind(move |a| OptionT::new(Result::Ok(Option::Some(a + 41))));
+
+        assert_eq!(o_t2.runner, Result::Ok(Option::None));
+    }
+
+    #[test]
+    fn option_t_lift() {
+        let lookup = |k: &'static str| {
+            State::new(move |s: Vec<(&str, i32)>| {
+                let found = s.iter().find(|(key, _)| *key == k).map(|(_, v)| *v);
+                (found, s)
+            })
+        };
+
+        let size: OptionT<State<_, Option<i32>>> =
+            MonadTrans::lift(State::new(|s: Vec<(&str, i32)>| (s.len() as i32, s)));
+        let prog = size.bind(move |len| {
+            OptionT::new(lookup("a").fmap(move |v: Option<i32>| v.map(|v| v + len)))
+        });
+
+        assert_eq!(
+            prog.execute().execute(vec![("a", 40), ("b", 1)]).0,
+            Some(42)
+        );
+    }
+
+    #[test]
+    fn option_t_or_else() {
+        let o_t = OptionT::new(Option::Some(Option::<i32>::None));
+
+        assert_eq!(
+            o_t.or_else(|| OptionT::new(Option::Some(Option::Some(42))))
+                .execute(),
+            Option::Some(Option::Some(42))
+        );
+
+        let o_t = OptionT::new(Option::Some(Option::Some(1)));
+
+        assert_eq!(
+            o_t.or_else(|| OptionT::new(Option::Some(Option::Some(42))))
+                .execute(),
+            Option::Some(Option::Some(1))
+        );
+    }
+
+    #[test]
+    fn option_t_result_t_conversions() {
+        let o_t = OptionT::new(Option::Some(Option::<i32>::None));
+        assert_eq!(
+            o_t.into_result_t("missing").execute(),
+            Option::Some(Result::Err("missing"))
+        );
+
+        let r_t = ResultT::new(Option::Some(Result::<i32, &str>::Err("boom")));
+        assert_eq!(
+            OptionT::from_result_t(r_t).execute(),
+            Option::Some(Option::None)
+        );
+
+        let r_t = ResultT::new(Option::Some(Result::<i32, &str>::Ok(42)));
+        assert_eq!(
+            OptionT::from_result_t(r_t).execute(),
+            Option::Some(Option::Some(42))
+        );
+    }
+
+    #[test]
+    fn option_t_many_parser() {
+        type Parser<'a, A> = OptionT<State<'a, &'a str, Option<A>>>;
+
+        fn item<'a>() -> Parser<'a, char> {
+            OptionT::new(State::new(|s: &str| {
+                let mut chars = s.chars();
+                match chars.next() {
+                    Some(c) => (Some(c), chars.as_str()),
+                    None => (None, s),
+                }
+            }))
+        }
+
+        // Backtrack on failure: the input is only consumed by a successful parse
+        fn digit<'a>() -> Parser<'a, u32> {
+            OptionT::new(State::new(|s: &str| match item().execute().execute(s) {
+                (Some(c), rest) if c.is_ascii_digit() => (c.to_digit(10), rest),
+                _ => (None, s),
+            }))
+        }
+
+        fn number<'a>() -> Parser<'a, u32> {
+            m! {
+                digits <- Parser::some(digit);
+                OptionT::new(State::new(move |s| {
+                    (Some(digits.iter().fold(0, |n, d| n * 10 + d)), s)
+                }))
+            }
+        }
+
+        assert_eq!(number().execute().execute("42abc"), (Some(42), "abc"));
+        assert_eq!(number().execute().execute("abc"), (None, "abc"));
+        assert_eq!(
+            Parser::many(digit).execute().execute("abc"),
+            (Some(vec![]), "abc")
+        );
+
+        let sign = || {
+            OptionT::new(State::new(|s: &str| match s.strip_prefix('-') {
+                Some(rest) => (Some(-1), rest),
+                None => (None, s),
+            }))
+            .alt(OptionT::new(State::new(|s| (Some(1), s))))
+        };
+        let signed = m! {
+            sign <- sign();
+            n <- number();
+            guard!(n < 100);
+            OptionT::new(State::new(move |s| (Some(sign * n as i32), s)))
+        };
+        assert_eq!(signed.execute().execute("-12!"), (Some(-12), "!"));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{Monad, MonadTrans};
+
+/// A `Reader` transformer monad parameterized by the environment type (R) and the inner monad (M)
+pub struct ReaderT<'a, R, M> {
+    /// The function which reads from the environment and return the inner Monad
+    pub runner: Box<dyn 'a + FnOnce(&R) -> M>,
+}
+
+impl<'a, A: 'a, R: 'a + Clone, M: 'a + Monad<'a, Unwrapped = A>> Monad<'a> for ReaderT<'a, R, M> {
+    type Unwrapped = A;
+    type Wrapped<B: 'a> = ReaderT<'a, R, M::Wrapped<B>>;
+
+    fn bind<F, B>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> Self::Wrapped<B> + 'a,
+    {
+        ReaderT {
+            runner: Box::new(move |r| {
+                let m = (self.runner)(r);
+
+                // The continuation may outlive the borrow of the environment,
+                // so it gets its own copy.
+                let env = r.clone();
+                m.bind(move |a| (f(a).runner)(&env))
+            }),
+        }
+    }
+
+    fn of<T: 'a>(value: T) -> Self::Wrapped<T> {
+        ReaderT {
+            runner: Box::new(move |_| M::of(value)),
+        }
+    }
+}
+
+impl<'a, R: 'a, M: 'a> ReaderT<'a, R, M> {
+    pub fn new<F>(runner: F) -> Self
+    where
+        F: FnOnce(&R) -> M + 'a,
+    {
+        Self {
+            runner: Box::new(runner),
+        }
+    }
+
+    pub fn execute(self, env: &R) -> M {
+        (self.runner)(env)
+    }
+
+    /// Retrieve the environment.
+    pub fn ask() -> Self
+    where
+        R: Clone,
+        M: Monad<'a, Unwrapped = R, Wrapped<R> = M>,
+    {
+        ReaderT::asks(R::clone)
+    }
+
+    /// Retrieve a value which is a function of the environment.
+    pub fn asks<A: 'a, F>(f: F) -> Self
+    where
+        M: Monad<'a, Unwrapped = A, Wrapped<A> = M>,
+        F: FnOnce(&R) -> A + 'a,
+    {
+        ReaderT::new(move |r| M::of(f(r)))
+    }
+
+    /// Execute a computation in a modified environment.
+    pub fn local<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&R) -> R + 'a,
+    {
+        ReaderT {
+            runner: Box::new(move |r| (self.runner)(&f(r))),
+        }
+    }
+}
+
+impl<'a, A: 'a, R: 'a + Clone, M: 'a + Monad<'a, Unwrapped = A>> MonadTrans<'a>
+    for ReaderT<'a, R, M>
+{
+    type Inner = M;
+
+    fn lift(base: M) -> Self {
+        ReaderT {
+            runner: Box::new(|_| base),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::m;
+    use crate::Monad;
+    use crate::MonadTrans;
+    use crate::ReaderT;
+
+    #[test]
+    fn reader_t_bind() {
+        let r_t = ReaderT::new(|env: &i32| Option::Some(env + 1));
+
+        let r_t2 = r_t.bind(|a| ReaderT::new(move |env: &i32| Option::Some(a + env)));
+
+        assert_eq!(r_t2.execute(&20), Option::Some(41));
+    }
+
+    #[test]
+    fn reader_t_bind_fail() {
+        let r_t = ReaderT::new(|env: &i32| Result::<i32, String>::Ok(env + 1));
+
+        let r_t2 =
+            r_t.bind(|_a| ReaderT::new(|env: &i32| Err::<i32, _>(format!("bad env {}", env))));
+
+        assert_eq!(r_t2.execute(&20), Err("bad env 20".to_string()));
+    }
+
+    #[test]
+    fn reader_t_lift() {
+        let lifted = ReaderT::lift(Option::Some(42));
+
+        let r_t = m! {
+            a <- lifted;
+            b <- ReaderT::new(|env: &String| Option::Some(env.len()));
+            ReaderT::lift(Option::Some(a + b))
+        };
+
+        assert_eq!(r_t.execute(&"FOO".to_string()), Option::Some(45));
+    }
+
+    #[test]
+    fn reader_t_ask_asks() {
+        let r_t = m! {
+            env <- ReaderT::<String, Option<_>>::ask();
+            len <- ReaderT::<_, Option<_>>::asks(|env: &String| env.len());
+            ReaderT::lift(if len > 0 { Option::Some(format!("{}:{}", env, len)) } else { Option::None })
+        };
+
+        assert_eq!(
+            r_t.execute(&"FOO".to_string()),
+            Option::Some("FOO:3".to_string())
+        );
+    }
+
+    #[test]
+    fn reader_t_local() {
+        let r_t = ReaderT::new(|env: &String| Option::Some(env.clone()));
+
+        assert_eq!(
+            r_t.local(|env| format!("{}_BAR", env))
+                .execute(&"FOO".to_string()),
+            Option::Some("FOO_BAR".to_string())
+        );
+    }
+}
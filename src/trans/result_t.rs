@@ -1,4 +1,7 @@
-use crate::Monad;
+use crate::{
+    Alternative, Bifunctor, Monad, MonadError, MonadPlus, MonadState, MonadTrans, MonadWrapped,
+    MonadWriter, MonadWriterWrapped, Monoid,
+};
 
 /// A `Result` (`Either`) transformer monad parameterized by the inner monad (M)
 pub struct ResultT<M> {
@@ -15,6 +18,7 @@ impl<'a, M> ResultT<M> {
         self.runner
     }
 
+    #[deprecated(note = "use `MonadTrans::lift`")]
     pub fn lift<E>(
         base: M,
     ) -> ResultT<<M as Monad<'a>>::Wrapped<Result<<M as Monad<'a>>::Unwrapped, E>>>
@@ -47,10 +51,184 @@ impl<'a, M: 'a + Monad<'a, Unwrapped = Result<A, E>>, A: 'a, E: 'a> Monad<'a> fo
     }
 }
 
+impl<'a, M, A: 'a, E: 'a> MonadTrans<'a> for ResultT<M>
+where
+    M: 'a + Monad<'a, Unwrapped = Result<A, E>>,
+    M::Wrapped<A>: Monad<'a, Unwrapped = A, Wrapped<Result<A, E>> = M>,
+{
+    type Inner = M::Wrapped<A>;
+
+    fn lift(base: Self::Inner) -> Self {
+        ResultT::new(base.bind::<_, Result<A, E>>(|a| M::Wrapped::<A>::of(Result::Ok(a))))
+    }
+}
+
+impl<'a, M, A: 'a, E: 'a> MonadWrapped<'a> for ResultT<M>
+where
+    M: 'a + MonadWrapped<'a, Unwrapped = Result<A, E>>,
+{
+    fn bind_wrapped<X: 'a, Y: 'a, F>(mx: Self::Wrapped<X>, f: F) -> Self::Wrapped<Y>
+    where
+        F: FnOnce(X) -> Self::Wrapped<Y> + 'a,
+    {
+        ResultT::new(M::bind_wrapped(mx.runner, |r| match r {
+            Ok(x) => f(x).runner,
+            Err(e) => M::of(Err(e)),
+        }))
+    }
+}
+
+/// `first` maps the error and `second` the value, inside the inner monad.
+impl<'a, M: 'a + Monad<'a, Unwrapped = Result<A, E>>, A: 'a, E: 'a> Bifunctor<'a> for ResultT<M> {
+    type First = E;
+    type Second = A;
+    type Wrapped<C: 'a, D: 'a> = ResultT<M::Wrapped<Result<D, C>>>;
+
+    fn bimap<F, G, C: 'a, D: 'a>(self, f: F, g: G) -> Self::Wrapped<C, D>
+    where
+        F: FnOnce(Self::First) -> C + 'a,
+        G: FnOnce(Self::Second) -> D + 'a,
+    {
+        ResultT::new(self.runner.bind(|r| M::of(r.bimap(f, g))))
+    }
+}
+
+/// `empty` fails with `E::mempty()`, and when both computations of `alt` fail
+/// their errors are combined.
+impl<'a, M, A: 'a, E: Monoid + 'a> Alternative<'a> for ResultT<M>
+where
+    M: 'a + Monad<'a, Unwrapped = Result<A, E>, Wrapped<Result<A, E>> = M>,
+{
+    fn empty() -> Self {
+        ResultT::new(M::of(Result::Err(E::mempty())))
+    }
+
+    /// The other computation only runs if this one fails.
+    fn alt(self, other: Self) -> Self {
+        ResultT::new(self.runner.bind::<_, Result<A, E>>(|r| {
+            match r {
+                Ok(a) => M::of(Ok(a)),
+                Err(e1) => other
+                    .runner
+                    .bind::<_, Result<A, E>>(|r| M::of(r.map_err(|e2| e1.mappend(e2)))),
+            }
+        }))
+    }
+}
+
+impl<'a, M, A: 'a, E: Monoid + 'a> MonadPlus<'a> for ResultT<M> where
+    M: 'a + Monad<'a, Unwrapped = Result<A, E>, Wrapped<Result<A, E>> = M>
+{
+}
+
+impl<'a, M, A: 'a, E: 'a> MonadError<'a, E> for ResultT<M>
+where
+    M: 'a + Monad<'a, Unwrapped = Result<A, E>, Wrapped<Result<A, E>> = M>,
+{
+    fn throw_error(e: E) -> Self {
+        ResultT::new(M::of(Result::Err(e)))
+    }
+
+    fn catch_error<F>(self, f: F) -> Self
+    where
+        F: FnOnce(E) -> Self + 'a,
+    {
+        ResultT::new(self.runner.bind::<_, Result<A, E>>(|r| match r {
+            Ok(a) => M::of(Ok(a)),
+            Err(e) => f(e).runner,
+        }))
+    }
+
+    fn attempt(self) -> Self::Wrapped<Result<Self::Unwrapped, E>> {
+        ResultT::new(
+            self.runner
+                .bind::<_, Result<Result<A, E>, E>>(|r| M::of(Ok(r))),
+        )
+    }
+}
+
+impl<'a, M, A: 'a, E: 'a, S> MonadState<'a, S> for ResultT<M>
+where
+    M: 'a + MonadState<'a, S> + Monad<'a, Unwrapped = Result<A, E>>,
+{
+    fn state<F>(f: F) -> Self
+    where
+        F: FnOnce(S) -> (A, S) + 'a,
+    {
+        ResultT::new(M::state(|s| {
+            let (a, s1) = f(s);
+            (Ok(a), s1)
+        }))
+    }
+}
+
+/// A failing computation keeps the log it wrote before failing.
+impl<'a, M, A: 'a, E: 'a, W: Monoid + 'a> MonadWriter<'a, W> for ResultT<M>
+where
+    M: 'a + MonadWriter<'a, W> + MonadWrapped<'a, Unwrapped = Result<A, E>>,
+    M::Wrapped<(Result<A, E>, W)>: Monad<
+        'a,
+        Unwrapped = (Result<A, E>, W),
+        Wrapped<Result<(A, W), E>> = M::Wrapped<Result<(A, W), E>>,
+    >,
+{
+    fn writer(a: A, w: W) -> Self {
+        ResultT::new(M::writer(Ok(a), w))
+    }
+
+    fn listen(self) -> Self::Wrapped<(A, W)>
+    where
+        W: Clone,
+    {
+        ResultT::new(self.runner.listen().bind::<_, Result<(A, W), E>>(|(r, w)| {
+            M::Wrapped::<(Result<A, E>, W)>::of(r.map(|a| (a, w)))
+        }))
+    }
+
+    fn censor<F>(self, f: F) -> Self
+    where
+        F: FnOnce(W) -> W + 'a,
+    {
+        ResultT::new(self.runner.censor(f))
+    }
+}
+
+impl<'a, M, A: 'a, E: 'a, W: Monoid + 'a> MonadWriterWrapped<'a, W> for ResultT<M>
+where
+    M: 'a + MonadWriterWrapped<'a, W> + MonadWrapped<'a, Unwrapped = Result<A, E>>,
+    M::Wrapped<(Result<A, E>, W)>: Monad<
+        'a,
+        Unwrapped = (Result<A, E>, W),
+        Wrapped<Result<(A, W), E>> = M::Wrapped<Result<(A, W), E>>,
+    >,
+{
+    /// A failing computation has no function to rewrite its log with, so its log is left untouched.
+    fn pass_wrapped<B: 'a, F>(m: Self::Wrapped<(B, F)>) -> Self::Wrapped<B>
+    where
+        F: FnOnce(W) -> W + 'a,
+    {
+        type Rewrite<'a, W> = Box<dyn 'a + FnOnce(W) -> W>;
+
+        ResultT::new(M::pass_wrapped(M::bind_wrapped(m.runner, |r| {
+            M::of(match r {
+                Ok((b, f)) => (Ok(b), Box::new(f) as Rewrite<W>),
+                Err(e) => (Err(e), Box::new(|w| w) as Rewrite<W>),
+            })
+        })))
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use crate::m;
+    use crate::Alternative;
+    use crate::Bifunctor;
     use crate::Monad;
+    use crate::MonadError;
+    use crate::MonadTrans;
+    use crate::MonadWriter;
     use crate::ResultT;
+    use crate::Writer;
 
     #[test]
     fn result_t_bind() {
@@ -70,6 +248,7 @@ mod test {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn result_t_lift() {
         let a = Option::Some(42);
         let lifted = ResultT::lift::<String>(a);
@@ -92,4 +271,144 @@ mod test {
             .execute()
             .is_none());
     }
+
+    #[test]
+    fn result_t_monad_trans_lift() {
+        let a = Option::Some(42);
+        let lifted: ResultT<Option<Result<i32, String>>> = MonadTrans::lift(a);
+
+        assert_eq!(
+            Result::Ok(43),
+            lifted
+                .bind(|a| ResultT::new(Option::Some(Ok(a + 1))))
+                .execute()
+                .unwrap()
+        );
+
+        // But if the value we lift is None, we get...
+        let b: Option<i32> = None;
+        let lifted2: ResultT<Option<Result<i32, String>>> = MonadTrans::lift(b);
+
+        assert!(lifted2
+            .bind(|a| ResultT::new(Option::Some(Ok(a + 1))))
+            .execute()
+            .is_none());
+    }
+
+    #[test]
+    fn result_t_bifunctor() {
+        #[derive(Debug, PartialEq)]
+        enum AppError {
+            Parse(String),
+        }
+
+        // An error of a lower layer, rewritten into the error of the application
+        let parsed = ResultT::new(Option::Some("4x".parse::<i32>()));
+        let app = parsed.first(|e| AppError::Parse(e.to_string()));
+
+        assert_eq!(
+            app.execute(),
+            Option::Some(Err(AppError::Parse(
+                "invalid digit found in string".to_string()
+            )))
+        );
+
+        let r_t = ResultT::new(Option::Some(Result::<i32, &str>::Ok(41)));
+        let r_t2 = r_t.bimap(|e| e.len(), |a| a + 1);
+        assert_eq!(r_t2.execute(), Option::Some(Ok(42)));
+    }
+
+    #[test]
+    fn result_t_alternative() {
+        let parse = |s: &'static str| {
+            ResultT::new(Option::Some(
+                s.parse::<i32>()
+                    .map_err(|_| vec![format!("{} is not a number", s)]),
+            ))
+        };
+
+        assert_eq!(parse("x").alt(parse("42")).execute(), Some(Ok(42)));
+        assert_eq!(
+            parse("x").alt(parse("y")).execute(),
+            Some(Err(vec![
+                "x is not a number".to_string(),
+                "y is not a number".to_string()
+            ]))
+        );
+        assert_eq!(
+            ResultT::<Option<Result<i32, Vec<String>>>>::empty().execute(),
+            Some(Err(vec![]))
+        );
+    }
+
+    #[test]
+    fn result_t_catch_error() {
+        let step = |n: i32| {
+            ResultT::new(Writer::new(
+                if n > 0 { Ok(n) } else { Err(n) },
+                format!("step {};", n),
+            ))
+        };
+
+        let recovered = step(-1).catch_error(|e| step(-e));
+        assert_eq!(
+            recovered.execute().execute(),
+            (Ok(1), "step -1;step 1;".into())
+        );
+
+        let handled = step(-2).handle_error_with(|e| e * 10);
+        assert_eq!(handled.execute().execute(), (Ok(-20), "step -2;".into()));
+
+        let attempted = step(-3).attempt();
+        assert_eq!(
+            attempted.execute().execute(),
+            (Ok(Err(-3)), "step -3;".into())
+        );
+    }
+
+    #[test]
+    fn result_t_tell_listen() {
+        type Logged<A> = ResultT<Writer<Result<A, String>, Vec<String>>>;
+
+        let half = |n: i32| -> Logged<i32> {
+            m! {
+                Logged::tell(vec![format!("half of {}", n)]);
+                if n % 2 == 0 {
+                    ResultT::new(Writer::new(Ok(n / 2), vec![]))
+                } else {
+                    Logged::throw_error(format!("{} is odd", n))
+                }
+            }
+        };
+
+        assert_eq!(
+            half(84).listen().execute().execute(),
+            (
+                Ok((42, vec!["half of 84".to_string()])),
+                vec!["half of 84".to_string()]
+            )
+        );
+        assert_eq!(
+            half(3)
+                .censor(|log| log.into_iter().map(|l| l.to_uppercase()).collect())
+                .execute()
+                .execute(),
+            (Err("3 is odd".to_string()), vec!["HALF OF 3".to_string()])
+        );
+
+        let shout = |n: i32| {
+            half(n).bind(|h| {
+                let upper = |log: Vec<String>| log.into_iter().map(|l| l.to_uppercase()).collect();
+                ResultT::new(Writer::new(Ok((h, upper)), vec![]))
+            })
+        };
+        assert_eq!(
+            shout(84).pass().execute().execute(),
+            (Ok(42), vec!["HALF OF 84".to_string()])
+        );
+        assert_eq!(
+            shout(3).pass().execute().execute(),
+            (Err("3 is odd".to_string()), vec!["half of 3".to_string()])
+        );
+    }
 }
@@ -0,0 +1,244 @@
+use crate::{Monad, MonadTrans, Monoid};
+
+/// A `RWS` transformer monad parameterized by the environment (R), the log (W),
+/// the state (S) and the inner monad (M)
+///
+/// A `RWST` is built by `new` from a runner `FnOnce(&R, S) -> M`, whose inner monad holds the value,
+/// the new state and the log of that step. Internally the log written so far is threaded through
+/// the computation together with the state, so every step only has to `bind` the inner monad once.
+#[allow(clippy::type_complexity)]
+pub struct RWST<'a, R, W: Monoid, S, M> {
+    /// The function which reads the environment and takes the state and the log written so far,
+    /// returning a Monad whose Unwrapped value is the tuple (_, S, W)
+    runner: Box<dyn 'a + FnOnce(&R, S, W) -> M>,
+}
+
+impl<'a, A: 'a, R: 'a + Clone, W: 'a + Monoid, S: 'a, M: 'a + Monad<'a, Unwrapped = (A, S, W)>>
+    Monad<'a> for RWST<'a, R, W, S, M>
+{
+    type Unwrapped = A;
+    type Wrapped<B: 'a> = RWST<'a, R, W, S, M::Wrapped<(B, S, W)>>;
+
+    fn bind<F, B>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> Self::Wrapped<B> + 'a,
+    {
+        RWST {
+            runner: Box::new(move |r, s, w| {
+                let m = (self.runner)(r, s, w);
+
+                // The continuation may outlive the borrow of the environment,
+                // so it gets its own copy.
+                let env = r.clone();
+                m.bind(move |(a1, s1, w1)| (f(a1).runner)(&env, s1, w1))
+            }),
+        }
+    }
+
+    fn of<T: 'a>(value: T) -> Self::Wrapped<T> {
+        RWST {
+            runner: Box::new(move |_, s, w| M::of((value, s, w))),
+        }
+    }
+}
+
+impl<'a, R: 'a, W: 'a + Monoid, S: 'a, M: 'a> RWST<'a, R, W, S, M> {
+    /// Constructs a new `RWST` by passing in the function which reads the environment,
+    /// uses and updates the state and returns the inner monad with the value, the state and the log
+    pub fn new<A: 'a, F>(runner: F) -> Self
+    where
+        M: Monad<'a, Unwrapped = (A, S, W), Wrapped<(A, S, W)> = M>,
+        F: FnOnce(&R, S) -> M + 'a,
+    {
+        RWST {
+            runner: Box::new(move |r, s, acc| {
+                runner(r, s).bind::<_, (A, S, W)>(move |(a, s1, w)| M::of((a, s1, acc.mappend(w))))
+            }),
+        }
+    }
+
+    /// Run a `RWST` by passing in the environment and some initial state, starting from an empty log.
+    pub fn run(self, env: &R, state: S) -> M {
+        (self.runner)(env, state, W::mempty())
+    }
+
+    /// Run a `RWST` and return the final value and the log, discarding the final state.
+    pub fn eval<A: 'a>(self, env: &R, state: S) -> M::Wrapped<(A, W)>
+    where
+        M: Monad<'a, Unwrapped = (A, S, W)>,
+    {
+        self.run(env, state).bind(|(a, _, w)| M::of((a, w)))
+    }
+
+    /// Run a `RWST` and return the final state and the log, discarding the final value.
+    pub fn exec<A: 'a>(self, env: &R, state: S) -> M::Wrapped<(S, W)>
+    where
+        M: Monad<'a, Unwrapped = (A, S, W)>,
+    {
+        self.run(env, state).bind(|(_, s, w)| M::of((s, w)))
+    }
+
+    /// Retrieve a value which is a function of the environment.
+    pub fn asks<A: 'a, F>(f: F) -> Self
+    where
+        M: Monad<'a, Unwrapped = (A, S, W), Wrapped<(A, S, W)> = M>,
+        F: FnOnce(&R) -> A + 'a,
+    {
+        RWST {
+            runner: Box::new(move |r, s, w| M::of((f(r), s, w))),
+        }
+    }
+
+    /// Retrieve a value which is a function of the current state.
+    pub fn gets<A: 'a, F>(f: F) -> Self
+    where
+        M: Monad<'a, Unwrapped = (A, S, W), Wrapped<(A, S, W)> = M>,
+        F: FnOnce(&S) -> A + 'a,
+    {
+        RWST {
+            runner: Box::new(move |_, s, w| M::of((f(&s), s, w))),
+        }
+    }
+
+    /// Retrieve the environment.
+    pub fn ask() -> Self
+    where
+        R: Clone,
+        M: Monad<'a, Unwrapped = (R, S, W), Wrapped<(R, S, W)> = M>,
+    {
+        RWST::asks(R::clone)
+    }
+
+    /// Retrieve the current state.
+    pub fn get() -> Self
+    where
+        S: Clone,
+        M: Monad<'a, Unwrapped = (S, S, W), Wrapped<(S, S, W)> = M>,
+    {
+        RWST::gets(S::clone)
+    }
+
+    /// Append `log` to the output.
+    pub fn tell(log: W) -> Self
+    where
+        M: Monad<'a, Unwrapped = ((), S, W), Wrapped<((), S, W)> = M>,
+    {
+        RWST {
+            runner: Box::new(move |_, s, w| M::of(((), s, w.mappend(log)))),
+        }
+    }
+
+    /// Replace the state.
+    pub fn put(state: S) -> Self
+    where
+        M: Monad<'a, Unwrapped = ((), S, W), Wrapped<((), S, W)> = M>,
+    {
+        RWST {
+            runner: Box::new(move |_, _, w| M::of(((), state, w))),
+        }
+    }
+
+    /// Update the state by applying `f`.
+    pub fn modify<F>(f: F) -> Self
+    where
+        M: Monad<'a, Unwrapped = ((), S, W), Wrapped<((), S, W)> = M>,
+        F: FnOnce(S) -> S + 'a,
+    {
+        RWST {
+            runner: Box::new(move |_, s, w| M::of(((), f(s), w))),
+        }
+    }
+
+    /// Execute a computation in a modified environment.
+    pub fn local<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&R) -> R + 'a,
+    {
+        RWST {
+            runner: Box::new(move |r, s, w| (self.runner)(&f(r), s, w)),
+        }
+    }
+}
+
+impl<'a, A: 'a, R: 'a + Clone, W: 'a + Monoid, S: 'a, M> MonadTrans<'a> for RWST<'a, R, W, S, M>
+where
+    M: 'a + Monad<'a, Unwrapped = (A, S, W)>,
+    M::Wrapped<A>: 'a + Monad<'a, Unwrapped = A, Wrapped<(A, S, W)> = M>,
+{
+    type Inner = M::Wrapped<A>;
+
+    fn lift(base: Self::Inner) -> Self {
+        RWST {
+            runner: Box::new(move |_, s, w| {
+                base.bind::<_, (A, S, W)>(move |a| M::Wrapped::<A>::of((a, s, w)))
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::m;
+    use crate::Monad;
+    use crate::MonadTrans;
+    use crate::RWST;
+
+    type Prog<'a, A> = RWST<'a, i32, Vec<String>, i32, Option<(A, i32, Vec<String>)>>;
+
+    #[test]
+    fn rws_t_bind() {
+        let rws_t = RWST::new(|r: &i32, s: i32| Option::Some((r + s, s, "FOO".to_string())));
+
+        let rws_t2 =
+            rws_t.bind(|a| RWST::new(move |_, s| Option::Some((a * 2, s + 1, "BAR".to_string()))));
+
+        assert_eq!(
+            rws_t2.run(&1, 20),
+            Option::Some((42, 21, "FOOBAR".to_string()))
+        );
+    }
+
+    #[test]
+    fn rws_t_bind_fail() {
+        let rws_t =
+            RWST::new(|r: &i32, s: i32| Result::<_, &str>::Ok((r + s, s, "FOO".to_string())));
+
+        let rws_t2 =
+            rws_t.bind(|_a| RWST::new(|_, _| Result::<(i32, i32, String), _>::Err("nope")));
+
+        assert_eq!(rws_t2.run(&1, 20), Err("nope"));
+    }
+
+    #[test]
+    fn rws_t_macro() {
+        let step = || -> Prog<()> {
+            m! {
+                incr <- Prog::asks(|r: &i32| *r);
+                count <- Prog::get();
+                Prog::tell(vec![format!("count: {}", count)]);
+                Prog::put(count + incr);
+                Prog::modify(|s| s * 2)
+            }
+        };
+
+        let prog: Prog<i32> = m! {
+            step();
+            step();
+            limit <- Prog::ask().local(|r| r * 5);
+            Prog::lift(if limit > 1 { Option::Some(limit) } else { Option::None })
+        };
+
+        assert_eq!(
+            prog.run(&1, 0),
+            Option::Some((5, 6, vec!["count: 0".to_string(), "count: 2".to_string()]))
+        );
+        assert_eq!(
+            step().eval(&1, 0),
+            Option::Some(((), vec!["count: 0".to_string()]))
+        );
+        assert_eq!(
+            step().exec(&1, 0),
+            Option::Some((2, vec!["count: 0".to_string()]))
+        );
+    }
+}
@@ -1,9 +1,102 @@
-use crate::Monad;
+use std::{cell::Cell, marker::PhantomData, ops::ControlFlow, rc::Rc};
+
+use crate::{
+    Eval, Monad, MonadError, MonadRec, MonadState, MonadTrans, MonadWrapped, MonadWriter,
+    MonadWriterWrapped, Monoid,
+};
 
 /// A `State` transformer monad parameterized by the state type (S) and the inner monad (M)
+///
+/// `StateT` is as stack-safe as its inner monad: over `Eval`, do-blocks built by (right-nested) `m!` loops
+/// run in constant stack space. Over `Option` and `Result` it is a `MonadRec`, so `tail_rec_m` loops never grow the stack.
+/// Over `Identity` it runs any chain of binds in constant stack space when it is executed as a `State`.
 pub struct StateT<'a, S, M> {
-    /// The "stateful" function which return a Monad whose Unwrapped value is the tuple (_, S)
-    pub runner: Box<dyn 'a + FnOnce(S) -> M>,
+    /// The "stateful" function which return a Monad whose Unwrapped value is the tuple (_, S),
+    /// or the binds it is made of
+    runner: Runner<'a, S, M>,
+}
+
+/// How a `StateT` runs: `bind` keeps its parts apart, so that they can also be run one after the other
+/// in the `Eval` loop instead of calling each other.
+enum Runner<'a, S, M> {
+    Lift(Box<dyn 'a + FnOnce(S) -> M>),
+    Bind(Box<dyn 'a + Bound<S, M>>),
+}
+
+/// A `StateT` followed by the continuation of a `bind`, hiding the type of the value passed between them.
+trait Bound<S, M> {
+    fn execute(self: Box<Self>, state: S) -> M;
+
+    fn trampoline<'b>(self: Box<Self>, state: S) -> Eval<'b, M>
+    where
+        Self: 'b;
+}
+
+struct Bind<'a, S, M, B, F> {
+    m: StateT<'a, S, M>,
+    f: F,
+    value: PhantomData<B>,
+}
+
+impl<'a, A: 'a, B: 'a, S: 'a, M, F> Bound<S, M::Wrapped<(B, S)>> for Bind<'a, S, M, B, F>
+where
+    M: 'a + Monad<'a, Unwrapped = (A, S)>,
+    M::Wrapped<(B, S)>: 'a,
+    F: FnOnce(A) -> StateT<'a, S, M::Wrapped<(B, S)>> + 'a,
+{
+    fn execute(self: Box<Self>, state: S) -> M::Wrapped<(B, S)> {
+        let Bind { m, f, .. } = *self;
+        m.run(state).bind(move |(a1, s1)| f(a1).run(s1))
+    }
+
+    fn trampoline<'b>(self: Box<Self>, state: S) -> Eval<'b, M::Wrapped<(B, S)>>
+    where
+        Self: 'b,
+    {
+        let Bind { m, f, .. } = *self;
+        Eval::defer(move || m.trampoline(state)).bind(move |m1: M| {
+            // The inner monad calls the continuation of `bind` once, right away (see `trampoline`)
+            let value = Rc::new(Cell::new(None));
+            let slot = value.clone();
+            let _ = m1.bind(move |v| {
+                slot.set(Some(v));
+                M::of(())
+            });
+            let (a1, s1) = value
+                .take()
+                .expect("the inner monad calls the continuation of bind right away");
+            f(a1).trampoline(s1)
+        })
+    }
+}
+
+impl<'a, S: 'a, M: 'a> StateT<'a, S, M> {
+    fn lift_runner<F>(runner: F) -> Self
+    where
+        F: FnOnce(S) -> M + 'a,
+    {
+        StateT {
+            runner: Runner::Lift(Box::new(runner)),
+        }
+    }
+
+    fn run(self, state: S) -> M {
+        match self.runner {
+            Runner::Lift(runner) => runner(state),
+            Runner::Bind(bound) => bound.execute(state),
+        }
+    }
+
+    /// Run the computation in the `Eval` loop: a chain of binds of any length runs in constant stack space.
+    ///
+    /// The value of each step is taken out of the inner monad with `bind`, whose continuation
+    /// must be called once, right away: `State` runs it over `Identity`.
+    pub(crate) fn trampoline(self, state: S) -> Eval<'a, M> {
+        match self.runner {
+            Runner::Lift(runner) => Eval::later(move || runner(state)),
+            Runner::Bind(bound) => bound.trampoline(state),
+        }
+    }
 }
 
 impl<'a, A: 'a, S: 'a, M: 'a + Monad<'a, Unwrapped = (A, S)>> Monad<'a> for StateT<'a, S, M> {
@@ -15,22 +108,16 @@ impl<'a, A: 'a, S: 'a, M: 'a + Monad<'a, Unwrapped = (A, S)>> Monad<'a> for Stat
         F: FnOnce(Self::Unwrapped) -> Self::Wrapped<B> + 'a,
     {
         StateT {
-            runner: Box::new(move |s| {
-                let m = (self.runner)(s);
-
-                let chain_fun = |v| {
-                    let (a1, s1) = v;
-                    (f(a1).runner)(s1)
-                };
-                m.bind(chain_fun)
-            }),
+            runner: Runner::Bind(Box::new(Bind {
+                m: self,
+                f,
+                value: PhantomData,
+            })),
         }
     }
 
     fn of<T: 'a>(value: T) -> Self::Wrapped<T> {
-        StateT {
-            runner: Box::new(move |s| M::of((value, s))),
-        }
+        StateT::lift_runner(move |s| M::of((value, s)))
     }
 }
 
@@ -39,29 +126,177 @@ impl<'a, S: 'a, M: 'a + Monad<'a>> StateT<'a, S, M> {
     where
         F: FnOnce(S) -> M + 'a,
     {
-        Self {
-            runner: Box::new(runner),
-        }
+        StateT::lift_runner(runner)
     }
 
     pub fn execute(self, state: S) -> M {
-        (self.runner)(state)
+        self.run(state)
     }
 
+    #[deprecated(note = "use `MonadTrans::lift`")]
     pub fn lift<N, B>(base: N) -> StateT<'a, S, <N as Monad<'a>>::Wrapped<(B, S)>>
     where
         N: Monad<'a, Unwrapped = B> + 'a,
     {
-        StateT {
-            runner: Box::new(|s| base.bind(|a| N::of((a, s)))),
-        }
+        StateT::lift_runner(|s| base.bind(|a| N::of((a, s))))
+    }
+}
+
+impl<'a, A: 'a, S: 'a, M> MonadTrans<'a> for StateT<'a, S, M>
+where
+    M: 'a + Monad<'a, Unwrapped = (A, S)>,
+    M::Wrapped<A>: 'a + Monad<'a, Unwrapped = A, Wrapped<(A, S)> = M>,
+{
+    type Inner = M::Wrapped<A>;
+
+    fn lift(base: Self::Inner) -> Self {
+        StateT::lift_runner(|s| base.bind::<_, (A, S)>(|a| M::Wrapped::<A>::of((a, s))))
+    }
+}
+
+impl<'a, A: 'a, S: 'a, M> MonadWrapped<'a> for StateT<'a, S, M>
+where
+    M: 'a + MonadWrapped<'a, Unwrapped = (A, S)>,
+{
+    fn bind_wrapped<X: 'a, Y: 'a, F>(mx: Self::Wrapped<X>, f: F) -> Self::Wrapped<Y>
+    where
+        F: FnOnce(X) -> Self::Wrapped<Y> + 'a,
+    {
+        StateT::lift_runner(move |s| M::bind_wrapped(mx.run(s), move |(x, s1)| f(x).run(s1)))
+    }
+}
+
+impl<'a, A: 'a, S: 'a> MonadRec<'a> for StateT<'a, S, Option<(A, S)>> {
+    fn tail_rec_m<B: 'a, C: 'a, F>(init: B, mut f: F) -> Self::Wrapped<C>
+    where
+        F: FnMut(B) -> Self::Wrapped<ControlFlow<C, B>> + 'a,
+    {
+        StateT::new(move |s| {
+            let (mut step, mut state) = f(init).execute(s)?;
+            loop {
+                match step {
+                    ControlFlow::Continue(b) => (step, state) = f(b).execute(state)?,
+                    ControlFlow::Break(c) => return Some((c, state)),
+                }
+            }
+        })
+    }
+}
+
+impl<'a, A: 'a, S: 'a, E: 'a> MonadRec<'a> for StateT<'a, S, Result<(A, S), E>> {
+    fn tail_rec_m<B: 'a, C: 'a, F>(init: B, mut f: F) -> Self::Wrapped<C>
+    where
+        F: FnMut(B) -> Self::Wrapped<ControlFlow<C, B>> + 'a,
+    {
+        StateT::new(move |s| {
+            let (mut step, mut state) = f(init).execute(s)?;
+            loop {
+                match step {
+                    ControlFlow::Continue(b) => (step, state) = f(b).execute(state)?,
+                    ControlFlow::Break(c) => return Ok((c, state)),
+                }
+            }
+        })
+    }
+}
+
+impl<'a, A: 'a, S: 'a, M> MonadState<'a, S> for StateT<'a, S, M>
+where
+    M: 'a + Monad<'a, Unwrapped = (A, S), Wrapped<(A, S)> = M>,
+{
+    fn state<F>(f: F) -> Self
+    where
+        F: FnOnce(S) -> (A, S) + 'a,
+    {
+        StateT::new(|s| M::of(f(s)))
+    }
+}
+
+impl<'a, A: 'a, S: 'a, W: Monoid + 'a, M> MonadWriter<'a, W> for StateT<'a, S, M>
+where
+    M: 'a + MonadWriter<'a, W> + MonadWrapped<'a, Unwrapped = (A, S)>,
+    M::Wrapped<((A, S), W)>:
+        Monad<'a, Unwrapped = ((A, S), W), Wrapped<((A, W), S)> = M::Wrapped<((A, W), S)>>,
+{
+    fn writer(a: A, w: W) -> Self {
+        StateT::new(|s| M::writer((a, s), w))
+    }
+
+    fn listen(self) -> Self::Wrapped<(A, W)>
+    where
+        W: Clone,
+    {
+        StateT::lift_runner(|s| {
+            self.run(s)
+                .listen()
+                .bind::<_, ((A, W), S)>(|((a, s1), w)| M::Wrapped::<((A, S), W)>::of(((a, w), s1)))
+        })
+    }
+
+    fn censor<F>(self, f: F) -> Self
+    where
+        F: FnOnce(W) -> W + 'a,
+    {
+        StateT::new(|s| self.run(s).censor(f))
+    }
+}
+
+impl<'a, A: 'a, S: 'a, W: Monoid + 'a, M> MonadWriterWrapped<'a, W> for StateT<'a, S, M>
+where
+    M: 'a + MonadWriterWrapped<'a, W> + MonadWrapped<'a, Unwrapped = (A, S)>,
+    M::Wrapped<((A, S), W)>:
+        Monad<'a, Unwrapped = ((A, S), W), Wrapped<((A, W), S)> = M::Wrapped<((A, W), S)>>,
+{
+    fn pass_wrapped<B: 'a, F>(m: Self::Wrapped<(B, F)>) -> Self::Wrapped<B>
+    where
+        F: FnOnce(W) -> W + 'a,
+    {
+        StateT::lift_runner(move |s| {
+            M::pass_wrapped(M::bind_wrapped(m.run(s), |((b, f), s1)| {
+                M::of(((b, s1), f))
+            }))
+        })
+    }
+}
+
+/// A failing computation is caught from the state it started with:
+/// the changes it made to the state before failing are discarded.
+impl<'a, A: 'a, S: Clone + 'a, E: 'a, M> MonadError<'a, E> for StateT<'a, S, M>
+where
+    M: 'a + MonadError<'a, E> + Monad<'a, Unwrapped = (A, S)>,
+    M::Wrapped<(Result<A, E>, S)>: MonadError<'a, E>,
+{
+    fn throw_error(e: E) -> Self {
+        StateT::new(|_| M::throw_error(e))
+    }
+
+    fn catch_error<F>(self, f: F) -> Self
+    where
+        F: FnOnce(E) -> Self + 'a,
+    {
+        StateT::new(move |s: S| self.run(s.clone()).catch_error(move |e| f(e).run(s)))
+    }
+
+    fn attempt(self) -> Self::Wrapped<Result<Self::Unwrapped, E>> {
+        StateT::new(move |s: S| {
+            self.run(s.clone())
+                .bind(|(a, s1)| M::of((Ok(a), s1)))
+                .catch_error(move |e| M::of((Err(e), s)))
+        })
     }
 }
 
 #[cfg(test)]
 mod test {
+    use crate::m;
+    use crate::Eval;
     use crate::Monad;
+    use crate::MonadError;
+    use crate::MonadState;
+    use crate::MonadTrans;
+    use crate::MonadWriter;
     use crate::StateT;
+    use crate::Writer;
 
     #[test]
     fn state_t_new_option() {
@@ -84,7 +319,7 @@ mod test {
         let s_t2 = s_t.bind(|a| StateT::new(move |s| Option::Some((a + 41, format!("{}_BAR", s)))));
 
         assert_eq!(
-            (s_t2.runner)("FOO".into()),
+            s_t2.execute("FOO".into()),
             Option::Some((42, "FOO_BAR".into()))
         );
     }
@@ -95,10 +330,11 @@ mod test {
 
         let s_t2 = s_t.bind(|_a| StateT::new(|_s| Option::<(i32, String)>::None));
 
-        assert_eq!((s_t2.runner)("FOO".into()), Option::None);
+        assert_eq!(s_t2.execute("FOO".into()), Option::None);
     }
 
     #[test]
+    #[allow(deprecated)]
     fn state_t_lift() {
         let a = Option::Some(42);
         let lifted = StateT::<&str, Option<i32>>::lift(a);
@@ -121,4 +357,120 @@ mod test {
             .execute("STATE")
             .is_none());
     }
+
+    #[test]
+    fn state_t_monad_trans_lift() {
+        let a = Option::Some(42);
+        let lifted: StateT<&str, Option<(i32, &str)>> = MonadTrans::lift(a);
+
+        assert_eq!(
+            Some((43, "STATE")),
+            lifted
+                .bind(|a| StateT::new(move |s| Option::Some((a + 1, s))))
+                .execute("STATE")
+        );
+
+        // But if the value we lift is None, we get...
+        let b: Option<i32> = None;
+
+        let lifted2: StateT<&str, Option<(i32, &str)>> = MonadTrans::lift(b);
+
+        assert!(lifted2
+            .bind(|a| StateT::new(move |s| Option::Some((a + 1, s))))
+            .execute("STATE")
+            .is_none());
+    }
+
+    #[test]
+    fn state_t_eval_loop() {
+        fn count_down<'a>(n: u32) -> StateT<'a, u64, Eval<'a, (u32, u64)>> {
+            if n == 0 {
+                StateT::new(|s| Eval::now((0, s)))
+            } else {
+                m! {
+                    _ <- StateT::new(|s: u64| Eval::now(((), s + 1)));
+                    count_down(n - 1)
+                }
+            }
+        }
+
+        assert_eq!(count_down(100_000).execute(0).run(), (0, 100_000));
+    }
+
+    #[test]
+    fn state_t_catch_error() {
+        let pop = || {
+            StateT::new(|mut s: Vec<i32>| match s.pop() {
+                Some(n) => Ok((n, s)),
+                None => Err("empty stack"),
+            })
+        };
+
+        let pop_two = || m! { a <- pop(); b <- pop(); StateT::new(move |s| Ok((a + b, s))) };
+
+        let prog = pop_two().catch_error(|_| pop());
+        assert_eq!(prog.execute(vec![1]), Ok((1, vec![])));
+
+        let prog = pop_two().handle_error_with(|e| e.len() as i32);
+        assert_eq!(prog.execute(vec![1]), Ok((11, vec![1])));
+
+        let prog = pop_two().attempt();
+        assert_eq!(prog.execute(vec![1, 2]), Ok((Ok(3), vec![])));
+
+        let thrown = StateT::<Vec<i32>, Result<(i32, Vec<i32>), &str>>::throw_error("nope");
+        assert_eq!(
+            thrown.attempt().execute(vec![1]),
+            Ok((Err("nope"), vec![1]))
+        );
+    }
+
+    #[test]
+    fn state_t_get_put() {
+        let checked_decr = || {
+            m! {
+                n <- StateT::<_, Option<_>>::get();
+                _ <- StateT::new(move |s| if n > 0 { Some(((), s)) } else { None });
+                StateT::put(n - 1)
+            }
+        };
+
+        assert_eq!(checked_decr().execute(1), Some(((), 0)));
+        assert_eq!(checked_decr().execute(0), None);
+    }
+
+    #[test]
+    fn state_t_tell_listen() {
+        type Counter<'a, A> = StateT<'a, i32, Writer<(A, i32), String>>;
+
+        let incr = || -> Counter<()> {
+            m! {
+                Counter::tell("incr;".to_string());
+                Counter::modify(|n| n + 1)
+            }
+        };
+
+        let prog = m! {
+            incr();
+            incr().listens(|log: &String| log.len())
+        };
+
+        assert_eq!(
+            prog.execute(40).execute(),
+            ((((), 5), 42), "incr;incr;".to_string())
+        );
+
+        let quiet = incr().censor(|_| String::new());
+        assert_eq!(quiet.execute(0).execute(), (((), 1), String::new()));
+
+        let loud = StateT::new(|n: i32| {
+            Writer::new(
+                ((n, |log: String| log.to_uppercase()), n + 1),
+                "incr;".to_string(),
+            )
+        });
+        assert_eq!(
+            loud.pass().execute(1).execute(),
+            ((1, 2), "INCR;".to_string())
+        );
+    }
 }
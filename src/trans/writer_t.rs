@@ -0,0 +1,256 @@
+use std::marker::PhantomData;
+
+use crate::{Monad, MonadState, MonadTrans, MonadWrapped, MonadWriter, MonadWriterWrapped, Monoid};
+
+/// A `Writer` transformer monad parameterized by the inner monad (M) and the log type (W).
+///
+/// `bind` appends the log written by the continuation to the log written so far, inside the inner monad:
+/// this is why the inner monad has to be a `MonadWrapped`.
+pub struct WriterT<M, W: Monoid> {
+    /// The inner monad whose Unwrapped value is the tuple (_, W)
+    pub runner: M,
+    log: PhantomData<W>,
+}
+
+impl<M, W: Monoid> WriterT<M, W> {
+    pub fn new(runner: M) -> Self {
+        Self {
+            runner,
+            log: PhantomData,
+        }
+    }
+
+    pub fn execute(self) -> M {
+        self.runner
+    }
+}
+
+impl<'a, A: 'a, W: 'a + Monoid, M: 'a + MonadWrapped<'a, Unwrapped = (A, W)>> Monad<'a>
+    for WriterT<M, W>
+{
+    type Unwrapped = A;
+    type Wrapped<B: 'a> = WriterT<M::Wrapped<(B, W)>, W>;
+
+    fn bind<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> Self::Wrapped<B> + 'a,
+    {
+        WriterT::new(self.runner.bind(move |(a1, w1)| {
+            M::bind_wrapped(f(a1).runner, move |(b, w2)| M::of((b, w1.mappend(w2))))
+        }))
+    }
+
+    fn of<T: 'a>(value: T) -> Self::Wrapped<T> {
+        WriterT::new(M::of((value, W::mempty())))
+    }
+}
+
+impl<'a, M: 'a, W: 'a + Monoid> WriterT<M, W> {
+    /// Construct a `WriterT` which only appends `log` to the output.
+    pub fn tell(log: W) -> Self
+    where
+        M: Monad<'a, Unwrapped = ((), W), Wrapped<((), W)> = M>,
+    {
+        WriterT::new(M::of(((), log)))
+    }
+
+    /// Execute the computation and add its own output to the value.
+    #[allow(clippy::type_complexity)]
+    pub fn listen<A: 'a>(self) -> WriterT<M::Wrapped<((A, W), W)>, W>
+    where
+        M: Monad<'a, Unwrapped = (A, W)>,
+        W: Clone,
+    {
+        WriterT::new(self.runner.bind(|(a, w)| M::of(((a, w.clone()), w))))
+    }
+
+    /// Execute the computation and apply `f` to its output, leaving the value unchanged.
+    pub fn censor<A: 'a, F>(self, f: F) -> WriterT<M::Wrapped<(A, W)>, W>
+    where
+        M: Monad<'a, Unwrapped = (A, W)>,
+        F: FnOnce(W) -> W + 'a,
+    {
+        WriterT::new(self.runner.bind(move |(a, w)| M::of((a, f(w)))))
+    }
+}
+
+/// The lifted computation doesn't write anything.
+impl<'a, A: 'a, W: 'a + Monoid, M> MonadTrans<'a> for WriterT<M, W>
+where
+    M: 'a + MonadWrapped<'a, Unwrapped = (A, W)>,
+    M::Wrapped<A>: 'a + Monad<'a, Unwrapped = A, Wrapped<(A, W)> = M>,
+{
+    type Inner = M::Wrapped<A>;
+
+    fn lift(base: Self::Inner) -> Self {
+        WriterT::new(base.bind::<_, (A, W)>(|a| M::Wrapped::<A>::of((a, W::mempty()))))
+    }
+}
+
+impl<'a, A: 'a, W: 'a + Monoid, M> MonadWriter<'a, W> for WriterT<M, W>
+where
+    M: 'a + MonadWrapped<'a, Unwrapped = (A, W), Wrapped<(A, W)> = M>,
+{
+    fn writer(a: A, w: W) -> Self {
+        WriterT::new(M::of((a, w)))
+    }
+
+    fn listen(self) -> Self::Wrapped<(A, W)>
+    where
+        W: Clone,
+    {
+        WriterT::listen(self)
+    }
+
+    fn censor<F>(self, f: F) -> Self
+    where
+        F: FnOnce(W) -> W + 'a,
+    {
+        WriterT::censor(self, f)
+    }
+}
+
+impl<'a, A: 'a, W: 'a + Monoid, M> MonadWriterWrapped<'a, W> for WriterT<M, W>
+where
+    M: 'a + MonadWrapped<'a, Unwrapped = (A, W), Wrapped<(A, W)> = M>,
+{
+    fn pass_wrapped<B: 'a, F>(m: Self::Wrapped<(B, F)>) -> Self::Wrapped<B>
+    where
+        F: FnOnce(W) -> W + 'a,
+    {
+        WriterT::new(M::bind_wrapped::<((B, F), W), (B, W), _>(
+            m.runner,
+            |((b, f), w)| M::of((b, f(w))),
+        ))
+    }
+}
+
+impl<'a, A: 'a, W: 'a + Monoid, S, M> MonadState<'a, S> for WriterT<M, W>
+where
+    M: 'a + MonadState<'a, S> + MonadWrapped<'a, Unwrapped = (A, W)>,
+{
+    fn state<F>(f: F) -> Self
+    where
+        F: FnOnce(S) -> (A, S) + 'a,
+    {
+        WriterT::new(M::state(move |s| {
+            let (a, s1) = f(s);
+            ((a, W::mempty()), s1)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::m;
+    use crate::Monad;
+    use crate::MonadState;
+    use crate::MonadTrans;
+    use crate::State;
+    use crate::StateT;
+    use crate::WriterT;
+
+    #[test]
+    fn writer_t_bind() {
+        let w_t = WriterT::new(Option::Some((1, "FOO".to_string())));
+
+        let w_t2 = w_t.bind(|a| WriterT::new(Option::Some((a + 41, "BAR".to_string()))));
+
+        assert_eq!(w_t2.execute(), Option::Some((42, "FOOBAR".to_string())));
+    }
+
+    #[test]
+    fn writer_t_bind_fail() {
+        let w_t = WriterT::new(Result::<_, String>::Ok((1, "FOO".to_string())));
+
+        let w_t2 = w_t.bind(|_a| WriterT::new(Result::<(i32, String), _>::Err("nope".into())));
+
+        assert_eq!(w_t2.execute(), Err("nope".to_string()));
+    }
+
+    #[test]
+    fn writer_t_lift_and_tell() {
+        let w_t = m! {
+            WriterT::<Option<_>, _>::tell(vec!["start"]);
+            a <- WriterT::<Option<_>, _>::lift(Option::Some(40));
+            WriterT::<Option<_>, _>::tell(vec!["lifted"]);
+            WriterT::lift(Option::Some(a + 2))
+        };
+
+        assert_eq!(w_t.execute(), Option::Some((42, vec!["start", "lifted"])));
+
+        let w_t = m! {
+            WriterT::<Option<_>, _>::tell(vec!["start"]);
+            _ <- WriterT::<Option<_>, _>::lift(Option::<i32>::None);
+            WriterT::tell(vec!["never"])
+        };
+
+        assert_eq!(w_t.execute(), Option::None);
+    }
+
+    #[test]
+    fn writer_t_listen_censor() {
+        let w_t = m! {
+            WriterT::<Option<_>, _>::tell("FOO".to_string());
+            WriterT::new(Option::Some((42, "BAR".to_string()))).listen()
+        };
+
+        assert_eq!(
+            w_t.execute(),
+            Option::Some(((42, "BAR".to_string()), "FOOBAR".to_string()))
+        );
+
+        let w_t = m! {
+            WriterT::<Option<_>, _>::tell("FOO".to_string());
+            WriterT::new(Option::Some((42, "bar".to_string()))).censor(|w| w.to_uppercase())
+        };
+
+        assert_eq!(w_t.execute(), Option::Some((42, "FOOBAR".to_string())));
+    }
+
+    #[test]
+    fn writer_t_over_state() {
+        let w_t = m! {
+            WriterT::<State<i32, _>, _>::tell(vec!["incr"]);
+            a <- WriterT::<State<i32, _>, _>::lift(State::new(|s: i32| (s, s + 1)));
+            WriterT::<State<i32, _>, _>::tell(vec!["double"]);
+            WriterT::lift(State::new(move |s: i32| (a, s * 2)))
+        };
+
+        assert_eq!(
+            w_t.execute().execute(20),
+            ((20, vec!["incr", "double"]), 42)
+        );
+    }
+
+    #[test]
+    fn writer_t_over_state_t() {
+        let w_t = m! {
+            WriterT::<StateT<i32, Option<_>>, _>::tell("get;".to_string());
+            a <- WriterT::<StateT<i32, Option<_>>, _>::lift(StateT::new(|s: i32| Option::Some((s, s))));
+            WriterT::lift(StateT::new(move |_s| Option::Some((a, a + 1))))
+        };
+
+        assert_eq!(
+            w_t.execute().execute(41),
+            Option::Some(((41, "get;".to_string()), 42))
+        );
+    }
+
+    #[test]
+    fn writer_t_get_put() {
+        let w_t: WriterT<State<i32, _>, _> = m! {
+            WriterT::<State<i32, _>, _>::tell(vec!["incr"]);
+            WriterT::<State<i32, _>, _>::modify(|s| s + 1);
+            n <- WriterT::<State<i32, _>, _>::get();
+            WriterT::<State<i32, _>, _>::tell(vec!["double"]);
+            WriterT::<State<i32, _>, _>::put(n * 2);
+            WriterT::gets(|s: &i32| s.to_string())
+        };
+
+        assert_eq!(
+            w_t.execute().execute(20),
+            (("42".to_string(), vec!["incr", "double"]), 42)
+        );
+    }
+}
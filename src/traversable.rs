@@ -1,4 +1,4 @@
-use crate::{Applicative, Foldable, Functor, Monoid};
+use crate::{Applicative, Functor};
 
 /// `Traversable` represents data structures which can be traversed while perserving the shape.
 /// Helpful to walk through a data structure from left to right,
@@ -8,14 +8,16 @@ pub trait Traversable<'a>: Functor<'a> {
     /// Convert elements to actions, then evaluate the actions from left-to-right
     /// and collect the results.
     ///
+    /// The action of an element can be mapped into the action of the whole structure, so a structure
+    /// without an empty value (like `NonEmpty`) can start from the action of its first element.
+    ///
     /// Haskell signature
     /// traverse  :: Applicative f => (a -> f b) -> t a -> f (t b)
     fn traverse<F, B: 'a, W>(self, f: F) -> W::Wrapped<Self::Wrapped<B>>
     where
         F: Fn(&Self::Unwrapped) -> W::Wrapped<B>,
-        W: Applicative<'a, Unwrapped = Self::Wrapped<B>, Wrapped<Self::Wrapped<B>> = W>
-            + 'a
-            + Monoid,
+        W: Applicative<'a, Unwrapped = Self::Wrapped<B>, Wrapped<Self::Wrapped<B>> = W> + 'a,
+        <W as Functor<'a>>::Wrapped<B>: Functor<'a, Unwrapped = B, Wrapped<Self::Wrapped<B>> = W>,
         <W as Functor<'a>>::Wrapped<Self::Wrapped<B>>: Applicative<'a>,
         <Self as Functor<'a>>::Wrapped<B>: 'a;
 
@@ -25,29 +27,25 @@ pub trait Traversable<'a>: Functor<'a> {
     /// sequenceA :: Applicative f => t (f a) -> f (t a)
     fn sequence_a<B: 'a, W>(self) -> W::Wrapped<Self::Wrapped<B>>
     where
-        W: Applicative<'a, Unwrapped = Self::Wrapped<B>, Wrapped<Self::Wrapped<B>> = W>
-            + 'a
-            + Monoid,
+        W: Applicative<'a, Unwrapped = Self::Wrapped<B>, Wrapped<Self::Wrapped<B>> = W> + 'a,
+        <W as Functor<'a>>::Wrapped<B>: Functor<'a, Unwrapped = B, Wrapped<Self::Wrapped<B>> = W>,
         W: Applicative<'a, Wrapped<B> = Self::Unwrapped>,
         <Self as Functor<'a>>::Wrapped<B>: 'a,
         Self::Unwrapped: Applicative<'a> + 'a + Copy;
 }
 
-impl<'a, A: Monoid> Traversable<'a> for Vec<A> {
+impl<'a, A> Traversable<'a> for Vec<A> {
     fn traverse<F, B: 'a, W>(self, f: F) -> W::Wrapped<Self::Wrapped<B>>
     where
         F: Fn(&Self::Unwrapped) -> W::Wrapped<B>,
-        W: Applicative<'a, Unwrapped = Self::Wrapped<B>, Wrapped<Self::Wrapped<B>> = W>
-            + 'a
-            + Monoid,
-        <W as Functor<'a>>::Wrapped<Self::Wrapped<B>>: Applicative<'a> + Monoid,
+        W: Applicative<'a, Unwrapped = Self::Wrapped<B>, Wrapped<Self::Wrapped<B>> = W> + 'a,
+        <W as Functor<'a>>::Wrapped<B>: Functor<'a, Unwrapped = B, Wrapped<Self::Wrapped<B>> = W>,
+        <W as Functor<'a>>::Wrapped<Self::Wrapped<B>>: Applicative<'a>,
         <Self as Functor<'a>>::Wrapped<B>: 'a,
     {
-        self.foldr(W::of(vec![]), |k, v| {
-            let c = f(v);
-
-            k.lift_a2(c, |mut acc, v: B| {
-                acc.insert(0, v);
+        self.iter().fold(W::of(vec![]), |acc, v| {
+            acc.lift_a2(f(v), |mut acc, v: B| {
+                acc.push(v);
                 acc
             })
         })
@@ -55,9 +53,8 @@ impl<'a, A: Monoid> Traversable<'a> for Vec<A> {
 
     fn sequence_a<B: 'a, W>(self) -> W::Wrapped<Self::Wrapped<B>>
     where
-        W: Applicative<'a, Unwrapped = Self::Wrapped<B>, Wrapped<Self::Wrapped<B>> = W>
-            + 'a
-            + Monoid,
+        W: Applicative<'a, Unwrapped = Self::Wrapped<B>, Wrapped<Self::Wrapped<B>> = W> + 'a,
+        <W as Functor<'a>>::Wrapped<B>: Functor<'a, Unwrapped = B, Wrapped<Self::Wrapped<B>> = W>,
         <Self as Functor<'a>>::Wrapped<B>: 'a,
         W: Applicative<'a, Wrapped<B> = Self::Unwrapped>,
         Self::Unwrapped: Applicative<'a> + 'a + Copy,
@@ -69,6 +66,7 @@ impl<'a, A: Monoid> Traversable<'a> for Vec<A> {
 #[cfg(test)]
 mod test {
     use crate::Traversable;
+    use crate::Validation;
 
     #[test]
     fn test_vec_option_traverse() {
@@ -105,4 +103,21 @@ mod test {
         let result = a.sequence_a::<_, Option<_>>();
         assert_eq!(None, result);
     }
+
+    #[test]
+    fn test_vec_traverse_effect_order() {
+        let a = vec![1, 2, 3];
+
+        let result = a.traverse::<_, i32, Validation<Vec<String>, _>>(|v| {
+            Validation::Failure(vec![format!("bad {}", v)])
+        });
+        assert_eq!(
+            Validation::Failure(vec![
+                "bad 1".to_string(),
+                "bad 2".to_string(),
+                "bad 3".to_string()
+            ]),
+            result
+        );
+    }
 }
@@ -0,0 +1,196 @@
+use crate::{Applicative, Apply, Functor, FunctorWrapped, Semigroup};
+
+/// `Validation` is like `Result`, but it doesn't stop at the first failure: when two validations
+/// are combined with `ap`/`lift_a2` and both fail, their errors are combined with `mappend`.
+/// This way every invalid field of a form is reported at once, e.g. with `E = Vec<FieldError>`.
+///
+/// Since combining needs to run both sides, `Validation` has no `Monad` instance: a computation
+/// depending on a previous result can go through `Result` (see the `From` conversions) and `and_then`.
+///
+/// Haskell definition from <https://hackage.haskell.org/package/validation/docs/Data-Validation.html>
+///
+/// ```haskell
+/// data Validation err a = Failure err | Success a
+///
+/// instance Semigroup err => Applicative (Validation err) where
+///   Failure e1 <*> Failure e2 = Failure (e1 <> e2)
+///   Failure e1 <*> Success _  = Failure e1
+///   Success _  <*> Failure e2 = Failure e2
+///   Success f  <*> Success a  = Success (f a)
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Validation<E, A> {
+    Failure(E),
+    Success(A),
+}
+
+impl<E, A> Validation<E, A> {
+    /// Whether the value is valid.
+    pub fn is_success(&self) -> bool {
+        matches!(self, Validation::Success(_))
+    }
+
+    /// Whether the value is invalid.
+    pub fn is_failure(&self) -> bool {
+        !self.is_success()
+    }
+}
+
+impl<E: Semigroup, A> Validation<E, A> {
+    /// Validate every element of `values` with `f`: the result holds all the valid values,
+    /// or all the errors when any of them is invalid.
+    pub fn traverse<T, F>(values: Vec<T>, f: F) -> Validation<E, Vec<A>>
+    where
+        F: Fn(T) -> Validation<E, A>,
+    {
+        values
+            .into_iter()
+            .fold(Validation::Success(vec![]), |acc, value| {
+                acc.lift_a2(f(value), |mut acc, a| {
+                    acc.push(a);
+                    acc
+                })
+            })
+    }
+}
+
+impl<E, A> From<Result<A, E>> for Validation<E, A> {
+    fn from(result: Result<A, E>) -> Self {
+        match result {
+            Ok(a) => Validation::Success(a),
+            Err(e) => Validation::Failure(e),
+        }
+    }
+}
+
+impl<E, A> From<Validation<E, A>> for Result<A, E> {
+    fn from(validation: Validation<E, A>) -> Self {
+        match validation {
+            Validation::Success(a) => Ok(a),
+            Validation::Failure(e) => Err(e),
+        }
+    }
+}
+
+impl<'a, E, A> Functor<'a> for Validation<E, A> {
+    type Unwrapped = A;
+
+    type Wrapped<B: 'a> = Validation<E, B>;
+
+    fn fmap<F, B: 'a>(self, f: F) -> Self::Wrapped<B>
+    where
+        F: Fn(Self::Unwrapped) -> B + 'a,
+    {
+        match self {
+            Validation::Success(a) => Validation::Success(f(a)),
+            Validation::Failure(e) => Validation::Failure(e),
+        }
+    }
+}
+
+impl<'a, E, A> FunctorWrapped<'a> for Validation<E, A> {
+    fn fmap_wrapped<X: 'a, Y: 'a, G>(fx: Self::Wrapped<X>, f: G) -> Self::Wrapped<Y>
+    where
+        G: Fn(X) -> Y + 'a,
+    {
+        fx.fmap(f)
+    }
+}
+
+impl<'a, E: Semigroup, A> Apply<'a> for Validation<E, A> {
+    /// As in Haskell the errors of the function come first.
+    fn ap<F, B: 'a>(self, f: Self::Wrapped<F>) -> Self::Wrapped<B>
+    where
+        F: FnOnce(Self::Unwrapped) -> B + 'a,
+    {
+        match (f, self) {
+            (Validation::Success(f), Validation::Success(a)) => Validation::Success(f(a)),
+            (Validation::Failure(e1), Validation::Failure(e2)) => {
+                Validation::Failure(e1.mappend(e2))
+            }
+            (Validation::Failure(e), _) | (_, Validation::Failure(e)) => Validation::Failure(e),
+        }
+    }
+
+    fn lift_a2<F, B: 'a, C: 'a>(self, b: Self::Wrapped<B>, f: F) -> Self::Wrapped<C>
+    where
+        F: FnOnce(Self::Unwrapped, B) -> C,
+    {
+        match (self, b) {
+            (Validation::Success(a), Validation::Success(b)) => Validation::Success(f(a, b)),
+            (Validation::Failure(e1), Validation::Failure(e2)) => {
+                Validation::Failure(e1.mappend(e2))
+            }
+            (Validation::Failure(e), _) | (_, Validation::Failure(e)) => Validation::Failure(e),
+        }
+    }
+}
+
+impl<'a, E: Semigroup, A: 'a> Applicative<'a> for Validation<E, A> {
+    fn of(value: Self::Unwrapped) -> Self::Wrapped<Self::Unwrapped> {
+        Validation::Success(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Apply;
+    use crate::Functor;
+    use crate::Validation;
+
+    fn positive(n: i32) -> Validation<Vec<String>, i32> {
+        if n > 0 {
+            Validation::Success(n)
+        } else {
+            Validation::Failure(vec![format!("{} is not positive", n)])
+        }
+    }
+
+    #[test]
+    fn validation_lift_a2_accumulates() {
+        assert_eq!(
+            positive(40).lift_a2(positive(2), |a, b| a + b),
+            Validation::Success(42)
+        );
+        assert_eq!(
+            positive(-1).lift_a2(positive(-2), |a, b| a + b),
+            Validation::Failure(vec![
+                "-1 is not positive".to_string(),
+                "-2 is not positive".to_string()
+            ])
+        );
+
+        let f = positive(0).fmap(|a| move |b: i32| a * b);
+        assert_eq!(
+            positive(-3).ap(f),
+            Validation::Failure(vec![
+                "0 is not positive".to_string(),
+                "-3 is not positive".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn validation_result_conversions() {
+        let v: Validation<String, i32> = "42".parse::<i32>().map_err(|e| e.to_string()).into();
+        assert_eq!(v, Validation::Success(42));
+
+        let r: Result<i32, Vec<String>> = positive(-1).into();
+        assert_eq!(r, Err(vec!["-1 is not positive".to_string()]));
+    }
+
+    #[test]
+    fn validation_traverse() {
+        assert_eq!(
+            Validation::traverse(vec![1, 2, 3], positive),
+            Validation::Success(vec![1, 2, 3])
+        );
+        assert_eq!(
+            Validation::traverse(vec![1, -2, 3, -4], positive),
+            Validation::Failure(vec![
+                "-2 is not positive".to_string(),
+                "-4 is not positive".to_string()
+            ])
+        );
+    }
+}
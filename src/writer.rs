@@ -1,4 +1,9 @@
-use crate::{Applicative, Apply, Functor, Monad, Monoid};
+use std::ops::ControlFlow;
+
+use crate::{
+    Applicative, Apply, Bifunctor, Functor, FunctorWrapped, Monad, MonadRec, MonadWrapped,
+    MonadWriter, MonadWriterWrapped, Monoid,
+};
 
 /// `Writer` helps capture the pattern of writing to a pure log or accumulated
 /// value, handling the book-keeping for you.
@@ -9,12 +14,12 @@ use crate::{Applicative, Apply, Functor, Monad, Monoid};
 /// one could use it for logging across processes and time, since the log
 /// is carried around with the result in a pure fashion. The monadic DSL
 /// helps make using these feel more natural.
-pub struct Writer<A, W: Monoid> {
+pub struct Writer<A, W> {
     /// The enclosed value and log of the `Writer`.
     runner: (A, W),
 }
 
-impl<A, W: Monoid> Writer<A, W> {
+impl<A, W> Writer<A, W> {
     /// Construct a `Writer` struct from a starting value and log.
     pub fn new(value: A, log: W) -> Self {
         Self {
@@ -47,6 +52,15 @@ impl<'a, A, W: Monoid> Functor<'a> for Writer<A, W> {
     }
 }
 
+impl<'a, A, W: Monoid> FunctorWrapped<'a> for Writer<A, W> {
+    fn fmap_wrapped<X: 'a, Y: 'a, G>(fx: Self::Wrapped<X>, f: G) -> Self::Wrapped<Y>
+    where
+        G: Fn(X) -> Y + 'a,
+    {
+        fx.fmap(f)
+    }
+}
+
 impl<'a, A, W: Monoid> Apply<'a> for Writer<A, W> {
     fn ap<F, B: 'a>(self, f: Self::Wrapped<F>) -> Self::Wrapped<B>
     where
@@ -93,11 +107,90 @@ impl<'a, A: 'a, W: Monoid> Monad<'a> for Writer<A, W> {
     }
 }
 
+impl<'a, A: 'a, W: Monoid> MonadRec<'a> for Writer<A, W> {
+    fn tail_rec_m<B: 'a, C: 'a, F>(init: B, mut f: F) -> Self::Wrapped<C>
+    where
+        F: FnMut(B) -> Self::Wrapped<ControlFlow<C, B>> + 'a,
+    {
+        let (mut step, mut log) = f(init).runner;
+        loop {
+            match step {
+                ControlFlow::Continue(b) => {
+                    let (next, w) = f(b).runner;
+                    step = next;
+                    log = log.mappend(w);
+                }
+                ControlFlow::Break(c) => return Writer::new(c, log),
+            }
+        }
+    }
+}
+
+impl<'a, A: 'a, W: Monoid> MonadWrapped<'a> for Writer<A, W> {
+    fn bind_wrapped<X: 'a, Y: 'a, F>(mx: Writer<X, W>, f: F) -> Writer<Y, W>
+    where
+        F: FnOnce(X) -> Writer<Y, W> + 'a,
+    {
+        mx.bind(f)
+    }
+}
+
+impl<'a, A: 'a, W: Monoid + 'a> MonadWriter<'a, W> for Writer<A, W> {
+    fn writer(a: A, w: W) -> Self {
+        Writer::new(a, w)
+    }
+
+    fn listen(self) -> Self::Wrapped<(A, W)>
+    where
+        W: Clone,
+    {
+        let (a, w) = self.runner;
+        Writer::new((a, w.clone()), w)
+    }
+
+    fn censor<F>(self, f: F) -> Self
+    where
+        F: FnOnce(W) -> W + 'a,
+    {
+        let (a, w) = self.runner;
+        Writer::new(a, f(w))
+    }
+}
+
+impl<'a, A: 'a, W: Monoid + 'a> MonadWriterWrapped<'a, W> for Writer<A, W> {
+    fn pass_wrapped<B: 'a, F>(m: Writer<(B, F), W>) -> Writer<B, W>
+    where
+        F: FnOnce(W) -> W + 'a,
+    {
+        let ((b, f), w) = m.runner;
+        Writer::new(b, f(w))
+    }
+}
+
+/// Maps the value (`first`) and the log (`second`), in the order of `execute`.
+impl<'a, A: 'a, W: 'a> Bifunctor<'a> for Writer<A, W> {
+    type First = A;
+    type Second = W;
+    type Wrapped<C: 'a, D: 'a> = Writer<C, D>;
+
+    fn bimap<F, G, C: 'a, D: 'a>(self, f: F, g: G) -> Self::Wrapped<C, D>
+    where
+        F: FnOnce(Self::First) -> C + 'a,
+        G: FnOnce(Self::Second) -> D + 'a,
+    {
+        let (a, w) = self.runner;
+        Writer::new(f(a), g(w))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::functor::Functor;
+    use crate::m;
     use crate::Apply;
+    use crate::Bifunctor;
     use crate::Monad;
+    use crate::MonadWriter;
     use crate::Writer;
 
     #[test]
@@ -129,4 +222,37 @@ mod test {
 
         assert_eq!(w2.runner, (42, "FOOBAR".into()));
     }
+
+    #[test]
+    fn writer_bifunctor() {
+        let w = Writer::new(41, vec!["start"]);
+        let w2 = w.bimap(|a| a + 1, |log| log.join(", "));
+        assert_eq!(w2.runner, (42, "start".to_string()));
+
+        // Turn the log into a different Monoid, to bind it with another Writer
+        let counted = Writer::new("a", vec![1, 2, 3]).second(|log| log.len() as i32);
+        let w3 = counted.bind(|a| Writer::new(a.repeat(2), 2));
+        assert_eq!(w3.execute(), ("aa".to_string(), 5));
+    }
+
+    #[test]
+    fn writer_listen_pass_censor() {
+        let w = m! {
+            Writer::<(), _>::tell(vec!["start"]);
+            Writer::new(42, vec!["answer"])
+        };
+        assert_eq!(
+            w.listen().execute(),
+            ((42, vec!["start", "answer"]), vec!["start", "answer"])
+        );
+
+        let counted = Writer::new(42, vec!["a", "b"]).listens(|log| log.len());
+        assert_eq!(counted.execute(), ((42, 2), vec!["a", "b"]));
+
+        let censored = Writer::new(42, "secret".to_string()).censor(|_| "*".repeat(6));
+        assert_eq!(censored.execute(), (42, "******".to_string()));
+
+        let passed = Writer::new((42, |w: String| w.to_uppercase()), "loud".to_string()).pass();
+        assert_eq!(passed.execute(), (42, "LOUD".to_string()));
+    }
 }